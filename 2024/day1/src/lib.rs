@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use aoc_core::{numbered_lines, parse_field, Answer, ParseError, Solution};
+
+pub struct Day1;
+
+fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
+    let mut first_numbers = Vec::new();
+    let mut second_numbers = Vec::new();
+
+    for (line_no, line) in numbered_lines(input) {
+        let numbers: Vec<&str> = line.split_whitespace().collect();
+
+        match numbers[..] {
+            [] => continue,
+            [first, second] => {
+                first_numbers.push(parse_field(line_no, line, first, "a location ID")?);
+                second_numbers.push(parse_field(line_no, line, second, "a location ID")?);
+            }
+            _ => {
+                return Err(ParseError::at(
+                    line_no,
+                    1,
+                    format!("expected two location IDs, found {} values", numbers.len()),
+                ))
+            }
+        }
+    }
+
+    first_numbers.sort();
+    second_numbers.sort();
+    Ok((first_numbers, second_numbers))
+}
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+
+    type Input = (Vec<i32>, Vec<i32>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1((first_numbers, second_numbers): &Self::Input) -> Answer {
+        let mut total = 0;
+
+        for (first, second) in first_numbers.iter().zip(second_numbers.iter()) {
+            let diff = (first - second).abs();
+            total += diff;
+            log::trace!("First: {}, Second: {}, Diff: {}", first, second, diff);
+        }
+
+        total.into()
+    }
+
+    fn part2((first_numbers, second_numbers): &Self::Input) -> Answer {
+        let mut count_map: HashMap<i32, i32> = HashMap::new();
+        for &second in second_numbers {
+            *count_map.entry(second).or_insert(0) += 1;
+        }
+
+        let mut total2 = 0;
+
+        for number in first_numbers {
+            if let Some(count) = count_map.get(number) {
+                total2 += number * count;
+            }
+        }
+
+        total2.into()
+    }
+}
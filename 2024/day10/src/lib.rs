@@ -0,0 +1,99 @@
+use std::collections::{HashSet, VecDeque};
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Grid, Point};
+
+pub struct Day10;
+
+fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let map = Grid::parse(input)?;
+    if let Some(pos) = map.find(|c| !c.is_ascii_digit()) {
+        return Err(ParseError::at(
+            pos.y as usize + 1,
+            pos.x as usize + 1,
+            format!("expected a height digit, found `{}`", map[pos]),
+        ));
+    }
+    Ok(map.map(|&c| c as u8 - b'0'))
+}
+
+// Find trailheads (height 0)
+fn trailheads(grid: &Grid<u8>) -> Vec<Point> {
+    grid.find_all(|&height| height == 0).collect()
+}
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 10;
+
+    type Input = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    // Part 1: Count reachable 9s
+    fn part1(grid: &Self::Input) -> Answer {
+        let mut part1_score = 0;
+        for start in trailheads(grid) {
+            part1_score += count_reachable_nines(grid, start);
+        }
+        part1_score.into()
+    }
+
+    // Part 2: Count distinct paths
+    fn part2(grid: &Self::Input) -> Answer {
+        let mut part2_score = 0;
+        for start in trailheads(grid) {
+            let mut visited = HashSet::new();
+            visited.insert(start);
+            part2_score += count_distinct_paths(grid, start, &mut visited, 0);
+        }
+        part2_score.into()
+    }
+}
+
+fn count_reachable_nines(grid: &Grid<u8>, start: Point) -> usize {
+    let mut queue = VecDeque::new();
+    let mut visited = HashSet::new();
+    let mut nines = HashSet::new();
+    queue.push_back((start, grid[start]));
+    visited.insert(start);
+
+    while let Some((pos, height)) = queue.pop_front() {
+        if height == 9 {
+            nines.insert(pos);
+            continue;
+        }
+
+        for next in grid.neighbours4(pos) {
+            if !visited.contains(&next) && grid[next] == height + 1 {
+                visited.insert(next);
+                queue.push_back((next, grid[next]));
+            }
+        }
+    }
+    nines.len()
+}
+
+fn count_distinct_paths(
+    grid: &Grid<u8>,
+    pos: Point,
+    visited: &mut HashSet<Point>,
+    height: u8
+) -> usize {
+    if height == 9 {
+        return 1;
+    }
+
+    let mut paths = 0;
+
+    for next in grid.neighbours4(pos) {
+        if !visited.contains(&next) && grid[next] == height + 1 {
+            visited.insert(next);
+            paths += count_distinct_paths(grid, next, visited, height + 1);
+            visited.remove(&next);
+        }
+    }
+    paths
+}
@@ -1,22 +1,39 @@
 use std::collections::HashMap;
-use std::fs;
+
+use aoc_core::{numbered_lines, parse_field, Answer, ParseError, Solution};
 use num_bigint::BigUint;
 use num_traits::{Zero, One};
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Failed to read input.txt");
-    let initial_stones: Vec<BigUint> = input
-        .split_whitespace()
-        .map(|s| s.trim().parse::<BigUint>().unwrap())
-        .collect();
+pub struct Day11;
+
+fn parse(input: &str) -> Result<Vec<BigUint>, ParseError> {
+    numbered_lines(input)
+        .flat_map(|(line_no, line)| {
+            line.split_whitespace()
+                .map(move |stone| parse_field(line_no, line, stone, "a stone number"))
+        })
+        .collect()
+}
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 11;
+
+    type Input = Vec<BigUint>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
 
     // Part 1: After 25 blinks
-    let total_stones_part1 = simulate_blinks(&initial_stones, 25);
-    println!("Part 1: Total stones after 25 blinks: {}", total_stones_part1);
+    fn part1(stones: &Self::Input) -> Answer {
+        simulate_blinks(stones, 25).into()
+    }
 
     // Part 2: After 75 blinks
-    let total_stones_part2 = simulate_blinks(&initial_stones, 75);
-    println!("Part 2: Total stones after 75 blinks: {}", total_stones_part2);
+    fn part2(stones: &Self::Input) -> Answer {
+        simulate_blinks(stones, 75).into()
+    }
 }
 
 fn simulate_blinks(initial_stones: &Vec<BigUint>, blinks: usize) -> usize {
@@ -0,0 +1,216 @@
+use std::fmt;
+
+use crate::policy::SafetyPolicy;
+
+/// What is wrong with a report as it stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Fewer than two levels, so there is no step to judge.
+    TooShort,
+    /// The step from `levels[index]` to `levels[index + 1]` breaks the policy.
+    Step {
+        index: usize,
+        from: i32,
+        to: i32,
+        fault: Fault,
+    },
+}
+
+/// How a step breaks the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Two equal levels where the policy forbids them.
+    Equal,
+    /// A fall in a report that rises, or the other way round.
+    Reversed {
+        increasing: bool,
+    },
+    TooSmall {
+        min: i32,
+    },
+    TooLarge {
+        max: i32,
+    },
+}
+
+/// Why a report is classified the way it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// The first step that breaks the policy, or `None` if the report is
+    /// safe as it stands.
+    pub problem: Option<Problem>,
+    /// The fewest levels to remove to make it safe, if the dampener can.
+    pub removed: Option<Vec<usize>>,
+}
+
+impl Diagnosis {
+    pub fn verdict(&self) -> &'static str {
+        match (&self.problem, &self.removed) {
+            (None, _) => "safe",
+            (Some(_), Some(_)) => "fixable",
+            (Some(_), None) => "unsafe",
+        }
+    }
+}
+
+/// Explains whether a report is safe under `policy`, pointing at the first
+/// step that breaks it and the removals (at most `k`) that would fix it.
+///
+/// When the report may go either way, it is judged in whichever direction it
+/// keeps to for longest.
+pub fn diagnose(levels: &[i32], k: usize, policy: &SafetyPolicy) -> Diagnosis {
+    let problem = if levels.len() < 2 {
+        Some(Problem::TooShort)
+    } else {
+        policy
+            .senses()
+            .iter()
+            .map(|&sense| first_problem(levels, policy, sense))
+            .max_by_key(|problem| match problem {
+                // On a tie, blame the step rather than the direction
+                Some(Problem::Step { index, fault, .. }) => {
+                    (*index, !matches!(fault, Fault::Reversed { .. }))
+                }
+                _ => (usize::MAX, true),
+            })
+            .flatten()
+    };
+    Diagnosis {
+        problem,
+        removed: crate::dampen(levels, k, policy),
+    }
+}
+
+fn first_problem(levels: &[i32], policy: &SafetyPolicy, sense: Option<bool>) -> Option<Problem> {
+    let index = levels
+        .windows(2)
+        .position(|pair| !policy.allows(pair[0], pair[1], sense))?;
+    let (from, to) = (levels[index], levels[index + 1]);
+    let diff = to as i64 - from as i64;
+    let fault = match sense {
+        _ if diff == 0 => Fault::Equal,
+        Some(increasing) if (diff > 0) != increasing => Fault::Reversed { increasing },
+        _ if diff.abs() < policy.min_step as i64 => Fault::TooSmall {
+            min: policy.min_step,
+        },
+        _ => Fault::TooLarge {
+            max: policy.max_step,
+        },
+    };
+    Some(Problem::Step {
+        index,
+        from,
+        to,
+        fault,
+    })
+}
+
+impl Fault {
+    /// A short name for machine-readable output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Fault::Equal => "equal",
+            Fault::Reversed { .. } => "reversed",
+            Fault::TooSmall { .. } => "too-small",
+            Fault::TooLarge { .. } => "too-large",
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Problem::Step {
+            index,
+            from,
+            to,
+            fault,
+        } = self
+        else {
+            return write!(f, "fewer than two levels");
+        };
+        write!(f, "index {}: {} -> {} ", index, from, to)?;
+        let step = (*to as i64 - *from as i64).abs();
+        match fault {
+            Fault::Equal => write!(f, "repeats a level"),
+            Fault::Reversed { increasing: true } => write!(f, "falls in a rising report"),
+            Fault::Reversed { increasing: false } => write!(f, "rises in a falling report"),
+            Fault::TooSmall { min } => write!(f, "moves by {}, less than {}", step, min),
+            Fault::TooLarge { max } => write!(f, "moves by {}, more than {}", step, max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    const PUZZLE: &SafetyPolicy = &SafetyPolicy::PUZZLE;
+
+    fn explained(levels: &[i32]) -> (String, &'static str, Option<Vec<usize>>) {
+        let diagnosis = diagnose(levels, 1, PUZZLE);
+        let problem = diagnosis
+            .problem
+            .as_ref()
+            .map_or("-".to_string(), |p| p.to_string());
+        (problem, diagnosis.verdict(), diagnosis.removed)
+    }
+
+    #[test]
+    fn test_diagnose_examples() {
+        assert_eq!(
+            explained(&[7, 6, 4, 2, 1]),
+            ("-".into(), "safe", Some(vec![]))
+        );
+        assert_eq!(
+            explained(&[1, 2, 7, 8, 9]),
+            (
+                "index 1: 2 -> 7 moves by 5, more than 3".into(),
+                "unsafe",
+                None
+            )
+        );
+        assert_eq!(
+            explained(&[1, 5, 2, 3]),
+            (
+                "index 0: 1 -> 5 moves by 4, more than 3".into(),
+                "fixable",
+                Some(vec![1])
+            )
+        );
+        assert_eq!(
+            explained(&[8, 6, 4, 4, 1]).0,
+            "index 2: 4 -> 4 repeats a level"
+        );
+        assert_eq!(
+            explained(&[3]),
+            ("fewer than two levels".into(), "unsafe", None)
+        );
+    }
+
+    #[test]
+    fn test_diagnose_follows_the_longer_direction() {
+        // Falling, it breaks at once; rising, only at the third step
+        let diagnosis = diagnose(&[1, 3, 5, 4], 0, PUZZLE);
+        assert_eq!(
+            diagnosis.problem,
+            Some(Problem::Step {
+                index: 2,
+                from: 5,
+                to: 4,
+                fault: Fault::Reversed { increasing: true },
+            })
+        );
+
+        let policy = &SafetyPolicy {
+            min_step: 2,
+            direction: Direction::NonMonotonic,
+            ..SafetyPolicy::PUZZLE
+        };
+        let diagnosis = diagnose(&[1, 3, 1, 2], 0, policy);
+        assert_eq!(
+            diagnosis.problem.unwrap().to_string(),
+            "index 2: 1 -> 2 moves by 1, less than 2"
+        );
+    }
+}
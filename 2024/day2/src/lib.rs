@@ -0,0 +1,279 @@
+use aoc_core::{numbered_lines, parse_field, Answer, ParseError, Solution};
+
+mod explain;
+mod policy;
+
+pub use explain::{diagnose, Diagnosis, Fault, Problem};
+pub use policy::{merge, Direction, Overrides, SafetyPolicy};
+
+pub struct Day2;
+
+/// Whether a report is safe as it stands under `policy`. A report needs at
+/// least two levels to be judged.
+pub fn check_sequence(numbers: &[i32], policy: &SafetyPolicy) -> bool {
+    numbers.len() >= 2
+        && policy.senses().iter().any(|&sense| {
+            numbers
+                .windows(2)
+                .all(|pair| policy.allows(pair[0], pair[1], sense))
+        })
+}
+
+/// Finds the fewest levels, at most `k`, whose removal leaves a report that
+/// is safe under `policy`, and returns their indices in ascending order. A
+/// report that is already safe needs no removals.
+///
+/// Takes O(n·k²) time and O(n·k) memory for a report of n levels, rather
+/// than trying every subset. That is linear in n only while `k` is small, as
+/// the dampener's one or two removals are: `k` is capped at n, but near that
+/// it grows to O(n³) time and O(n²) memory.
+pub fn dampen(levels: &[i32], k: usize, policy: &SafetyPolicy) -> Option<Vec<usize>> {
+    // Removing more levels than there are means nothing, and a larger `k`
+    // would only size the table past what fits
+    let k = k.min(levels.len());
+    policy
+        .senses()
+        .iter()
+        .filter_map(|&sense| dampen_towards(levels, k, policy, sense))
+        .min_by_key(|removed| removed.len())
+}
+
+// Keeping level `i` after removing `r` of the levels before it is a state,
+// stored at `i * (k + 1) + r` with the previous kept level (or `None` when
+// `i` is the first). Each state can only be reached from the k + 1 - r
+// nearest kept levels behind it, so the pass does O(k²) work per level.
+fn dampen_towards(
+    levels: &[i32],
+    k: usize,
+    policy: &SafetyPolicy,
+    sense: Option<bool>,
+) -> Option<Vec<usize>> {
+    let n = levels.len();
+    let width = k + 1;
+    let mut previous: Vec<Option<Option<usize>>> = vec![None; n * width];
+
+    for i in 0..n {
+        if i <= k {
+            previous[i * width + i] = Some(None);
+        }
+        for r in 0..width {
+            if previous[i * width + r].is_none() {
+                continue;
+            }
+            for next in i + 1..(i + 1 + width - r).min(n) {
+                let removed = r + next - i - 1;
+                let state = &mut previous[next * width + removed];
+                if state.is_none() && policy.allows(levels[i], levels[next], sense) {
+                    *state = Some(Some(i));
+                }
+            }
+        }
+    }
+
+    // Finish at the last kept level, dropping everything after it
+    let (mut i, mut r) = (0..n)
+        .flat_map(|i| (0..width).map(move |r| (i, r)))
+        .filter(|&(i, r)| previous[i * width + r].is_some())
+        .filter(|&(i, r)| r + n - 1 - i <= k && i + 1 - r >= 2)
+        .min_by_key(|&(i, r)| r + n - 1 - i)?;
+
+    let mut kept = vec![false; n];
+    kept[i] = true;
+    while let Some(Some(before)) = previous[i * width + r] {
+        r -= i - before - 1;
+        i = before;
+        kept[i] = true;
+    }
+    Some((0..n).filter(|&i| !kept[i]).collect())
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    numbered_lines(input)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_no, line)| {
+            line.split_whitespace()
+                .map(|level| parse_field(line_no, line, level, "a level"))
+                .collect()
+        })
+        .collect()
+}
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 2;
+
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(reports: &Self::Input) -> Answer {
+        reports
+            .iter()
+            .filter(|numbers| check_sequence(numbers, &SafetyPolicy::PUZZLE))
+            .count()
+            .into()
+    }
+
+    fn part2(reports: &Self::Input) -> Answer {
+        let mut safe = 0;
+
+        for numbers in reports {
+            let diagnosis = diagnose(numbers, 1, &SafetyPolicy::PUZZLE);
+            match (&diagnosis.problem, &diagnosis.removed) {
+                (None, _) => {
+                    safe += 1;
+                    log::debug!("Originally safe sequence: {:?}", numbers);
+                }
+                (Some(problem), Some(removed)) => {
+                    safe += 1;
+                    log::debug!(
+                        "Fixable sequence: {:?} ({}) by removing {:?}",
+                        numbers,
+                        problem,
+                        removed
+                    );
+                }
+                (Some(problem), None) => {
+                    log::debug!("Unsafe sequence: {:?} ({})", numbers, problem);
+                }
+            }
+        }
+
+        safe.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    const PUZZLE: &SafetyPolicy = &SafetyPolicy::PUZZLE;
+
+    // Tries every way of removing up to `k` levels
+    fn brute_force(levels: &[i32], k: usize, policy: &SafetyPolicy) -> Option<usize> {
+        (0..1u32 << levels.len())
+            .filter(|mask| mask.count_ones() as usize <= k)
+            .filter(|mask| {
+                let kept: Vec<_> = (0..levels.len())
+                    .filter(|i| mask & 1 << i == 0)
+                    .map(|i| levels[i])
+                    .collect();
+                check_sequence(&kept, policy)
+            })
+            .map(|mask| mask.count_ones() as usize)
+            .min()
+    }
+
+    #[test]
+    fn test_examples() {
+        let reports = parse(TEST_INPUT).unwrap();
+        assert_eq!(Day2::part1(&reports), Answer::Number(2));
+        assert_eq!(Day2::part2(&reports), Answer::Number(4));
+    }
+
+    #[test]
+    fn test_dampen_returns_removed_indices() {
+        assert_eq!(dampen(&[7, 6, 4, 2, 1], 1, PUZZLE), Some(vec![]));
+        assert_eq!(dampen(&[1, 5, 2, 3], 1, PUZZLE), Some(vec![1]));
+        assert_eq!(dampen(&[8, 6, 9, 4], 1, PUZZLE), Some(vec![2]));
+        assert_eq!(dampen(&[1, 2, 7, 8, 9], 1, PUZZLE), None);
+        // Dropping the first level can settle the direction
+        assert_eq!(dampen(&[5, 1, 2, 3], 1, PUZZLE), Some(vec![0]));
+        assert_eq!(dampen(&[1, 2, 3, 9], 1, PUZZLE), Some(vec![3]));
+    }
+
+    #[test]
+    fn test_dampen_removes_more_levels() {
+        assert_eq!(dampen(&[1, 9, 2, 9, 3], 1, PUZZLE), None);
+        assert_eq!(dampen(&[1, 9, 2, 9, 3], 2, PUZZLE), Some(vec![1, 3]));
+        assert_eq!(dampen(&[1, 9, 9, 2, 3], 2, PUZZLE), Some(vec![1, 2]));
+        // At least two levels have to stay
+        assert_eq!(dampen(&[1, 1, 1], 1, PUZZLE), None);
+        assert_eq!(dampen(&[1, 1, 1], 2, PUZZLE), None);
+    }
+
+    #[test]
+    fn test_dampen_any_number_of_removals() {
+        // No more levels can go than a report has, however many are allowed
+        assert_eq!(
+            dampen(&[1, 9, 2, 9, 3], usize::MAX, PUZZLE),
+            Some(vec![1, 3])
+        );
+        assert_eq!(dampen(&[1, 1, 1], usize::MAX, PUZZLE), None);
+        assert_eq!(dampen(&[], usize::MAX, PUZZLE), None);
+    }
+
+    #[test]
+    fn test_other_policies() {
+        let increasing = &SafetyPolicy {
+            direction: Direction::Increasing,
+            ..SafetyPolicy::PUZZLE
+        };
+        assert!(!check_sequence(&[7, 6, 4, 2, 1], increasing));
+        assert!(check_sequence(&[1, 3, 6, 7, 9], increasing));
+        assert_eq!(dampen(&[1, 5, 2, 3], 1, increasing), Some(vec![1]));
+
+        let loose = &SafetyPolicy {
+            max_step: 5,
+            direction: Direction::NonMonotonic,
+            allow_equal: true,
+            ..SafetyPolicy::PUZZLE
+        };
+        assert!(check_sequence(&[1, 3, 2, 7, 7, 5], loose));
+        assert_eq!(dampen(&[1, 3, 2, 9, 7, 5], 1, loose), Some(vec![3]));
+    }
+
+    #[test]
+    fn test_dampen_matches_brute_force() {
+        let reports = parse(TEST_INPUT).unwrap();
+        let extra = [vec![3, 1, 4, 1, 5, 9, 2, 6], vec![10, 7, 8, 5, 20, 4, 1, 1]];
+        let policies = [
+            SafetyPolicy::PUZZLE,
+            SafetyPolicy {
+                min_step: 0,
+                max_step: 4,
+                direction: Direction::Decreasing,
+                allow_equal: true,
+            },
+            SafetyPolicy {
+                min_step: 2,
+                max_step: 6,
+                direction: Direction::NonMonotonic,
+                allow_equal: false,
+            },
+        ];
+        for policy in &policies {
+            for levels in reports.iter().chain(&extra) {
+                for k in 0..4 {
+                    let removed = dampen(levels, k, policy);
+                    assert_eq!(
+                        removed.as_ref().map(Vec::len),
+                        brute_force(levels, k, policy)
+                    );
+                    if let Some(removed) = removed {
+                        let kept: Vec<_> = (0..levels.len())
+                            .filter(|i| !removed.contains(i))
+                            .map(|i| levels[i])
+                            .collect();
+                        assert!(
+                            check_sequence(&kept, policy),
+                            "{:?} without {:?}",
+                            levels,
+                            removed
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use day2::{dampen, diagnose, merge, parse, Diagnosis, Overrides, Problem, SafetyPolicy};
+
+const USAGE: &str = "\
+Usage:
+    day2 [--policy <file>] [--min-step <n>] [--max-step <n>]
+         [--direction <dir>] [--[no-]allow-equal] [--removals <k>]
+         [--explain [--format <format>]] [<path> | -]
+
+Counts the reports that are safe after removing at most <k> levels, reading
+them from <path>, stdin (`-`) or this day's input.txt.
+
+    --policy <file>      TOML with any of min_step, max_step, direction and
+                         allow_equal; flags override it
+    --min-step <n>       smallest step between adjacent levels (default 1)
+    --max-step <n>       largest step between adjacent levels (default 3)
+    --direction <dir>    increasing, decreasing, either (default) or
+                         non-monotonic
+    --allow-equal        let adjacent levels be equal
+    --no-allow-equal     forbid equal adjacent levels (the default)
+    --removals <k>       levels the dampener may remove (default 1; 0 is
+                         part 1). Each report costs about n*k*k steps for
+                         n levels, so keep k small
+    --explain            classify every report first: the first step that
+                         breaks the policy and the removals that fix it
+    --format <format>    text (default) for a table, or json for one object
+                         per report and no total";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug)]
+struct Options {
+    policy: SafetyPolicy,
+    removals: usize,
+    explain: Option<Format>,
+    input: Option<PathBuf>,
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid {} `{}`", flag, value))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut policy_file = None;
+    let mut flags = Overrides::default();
+    let mut removals = 1;
+    let mut explain = false;
+    let mut format = Format::Text;
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--policy" => policy_file = Some(args.next().ok_or("--policy needs a value")?),
+            "--min-step" => flags.min_step = Some(parse_number(&arg, args.next())?),
+            "--max-step" => flags.max_step = Some(parse_number(&arg, args.next())?),
+            "--direction" => {
+                flags.direction = Some(args.next().ok_or("--direction needs a value")?.parse()?);
+            }
+            "--allow-equal" => flags.allow_equal = Some(true),
+            "--no-allow-equal" => flags.allow_equal = Some(false),
+            "--removals" => removals = parse_number(&arg, args.next())?,
+            "--explain" => explain = true,
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    Some(other) => return Err(format!("unknown format `{}`", other)),
+                    None => return Err("--format needs a value".to_string()),
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown flag `{}`", arg)),
+            _ if input.is_none() => input = Some(arg),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let file = match policy_file {
+        Some(path) => {
+            let text =
+                fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+            SafetyPolicy::from_toml(&text).map_err(|e| format!("{}: {}", path, e))?
+        }
+        None => SafetyPolicy::PUZZLE,
+    };
+    let policy = merge(file, flags)?;
+
+    let input = match input.as_deref() {
+        Some("-") => None,
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("input.txt")),
+    };
+    Ok(Options {
+        policy,
+        removals,
+        explain: explain.then_some(format),
+        input,
+    })
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let (name, input) = match &options.input {
+        Some(path) => {
+            let input = fs::read_to_string(path)
+                .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+            (path.display().to_string(), input)
+        }
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("cannot read stdin: {}", e))?;
+            ("stdin".to_string(), input)
+        }
+    };
+    let reports = parse(&input).map_err(|e| format!("{}: {}", name, e))?;
+    match options.explain {
+        Some(Format::Json) => {
+            for (i, levels) in reports.iter().enumerate() {
+                let diagnosis = diagnose(levels, options.removals, &options.policy);
+                println!("{}", json_record(i + 1, levels, &diagnosis));
+            }
+            return Ok(());
+        }
+        Some(Format::Text) => print_table(&reports, options),
+        None => {}
+    }
+    let safe = reports
+        .iter()
+        .filter(|levels| dampen(levels, options.removals, &options.policy).is_some())
+        .count();
+    println!("{}", safe);
+    Ok(())
+}
+
+fn print_table(reports: &[Vec<i32>], options: &Options) {
+    let rows: Vec<[String; 5]> = reports
+        .iter()
+        .enumerate()
+        .map(|(i, levels)| {
+            let diagnosis = diagnose(levels, options.removals, &options.policy);
+            let problem = diagnosis
+                .problem
+                .as_ref()
+                .map_or("-".to_string(), |p| p.to_string());
+            let fix = match &diagnosis.removed {
+                Some(removed) if removed.len() == 1 => format!("remove index {}", removed[0]),
+                Some(removed) if !removed.is_empty() => {
+                    let removed: Vec<_> = removed.iter().map(|i| i.to_string()).collect();
+                    format!("remove indices {}", removed.join(", "))
+                }
+                _ => "-".to_string(),
+            };
+            let levels: Vec<_> = levels.iter().map(|n| n.to_string()).collect();
+            [
+                (i + 1).to_string(),
+                diagnosis.verdict().to_string(),
+                problem,
+                fix,
+                levels.join(" "),
+            ]
+        })
+        .collect();
+
+    let header = ["report", "verdict", "problem", "fix", "levels"].map(String::from);
+    let mut widths = [0; 4];
+    for row in std::iter::once(&header).chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in std::iter::once(&header).chain(&rows) {
+        println!(
+            "{:>w0$}  {:<w1$}  {:<w2$}  {:<w3$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+    }
+}
+
+fn json_record(report: usize, levels: &[i32], diagnosis: &Diagnosis) -> String {
+    let list = |ns: Vec<String>| format!("[{}]", ns.join(","));
+    let problem = match &diagnosis.problem {
+        None => "null".to_string(),
+        Some(Problem::TooShort) => r#"{"fault":"too-short"}"#.to_string(),
+        Some(Problem::Step {
+            index,
+            from,
+            to,
+            fault,
+        }) => format!(
+            r#"{{"index":{},"from":{},"to":{},"fault":"{}"}}"#,
+            index,
+            from,
+            to,
+            fault.name()
+        ),
+    };
+    let removed = match &diagnosis.removed {
+        Some(removed) => list(removed.iter().map(|i| i.to_string()).collect()),
+        None => "null".to_string(),
+    };
+    format!(
+        r#"{{"report":{},"levels":{},"verdict":"{}","problem":{},"removed":{}}}"#,
+        report,
+        list(levels.iter().map(|n| n.to_string()).collect()),
+        diagnosis.verdict(),
+        problem,
+        removed
+    )
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use std::str::FromStr;
+
+/// Which way the levels of a safe report may move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// All increasing or all decreasing, as in the puzzle.
+    #[default]
+    Either,
+    /// Any mix of rises and falls.
+    NonMonotonic,
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Direction, String> {
+        match s {
+            "increasing" => Ok(Direction::Increasing),
+            "decreasing" => Ok(Direction::Decreasing),
+            "either" => Ok(Direction::Either),
+            "non-monotonic" | "any" => Ok(Direction::NonMonotonic),
+            _ => Err(format!(
+                "unknown direction `{}` (expected increasing, decreasing, either or non-monotonic)",
+                s
+            )),
+        }
+    }
+}
+
+/// What makes a report safe: every step between adjacent levels moves
+/// between `min_step` and `max_step` (inclusive) in the allowed direction.
+/// With `allow_equal`, adjacent levels may also be equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    pub min_step: i32,
+    pub max_step: i32,
+    pub direction: Direction,
+    pub allow_equal: bool,
+}
+
+/// Settings given on top of a policy, such as command-line flags; `None`
+/// keeps the policy's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Overrides {
+    pub min_step: Option<i32>,
+    pub max_step: Option<i32>,
+    pub direction: Option<Direction>,
+    pub allow_equal: Option<bool>,
+}
+
+/// Lays `flags` over the policy read from a file (or the puzzle's), so that
+/// each flag given wins and every other setting stays, then checks the
+/// result.
+pub fn merge(file: SafetyPolicy, flags: Overrides) -> Result<SafetyPolicy, String> {
+    let policy = SafetyPolicy {
+        min_step: flags.min_step.unwrap_or(file.min_step),
+        max_step: flags.max_step.unwrap_or(file.max_step),
+        direction: flags.direction.unwrap_or(file.direction),
+        allow_equal: flags.allow_equal.unwrap_or(file.allow_equal),
+    };
+    policy.validate()?;
+    Ok(policy)
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> SafetyPolicy {
+        SafetyPolicy::PUZZLE
+    }
+}
+
+impl SafetyPolicy {
+    /// The puzzle's rules: strictly increasing or decreasing by 1 to 3.
+    pub const PUZZLE: SafetyPolicy = SafetyPolicy {
+        min_step: 1,
+        max_step: 3,
+        direction: Direction::Either,
+        allow_equal: false,
+    };
+
+    /// Reads a policy from TOML such as
+    ///
+    /// ```toml
+    /// min_step = 1
+    /// max_step = 4
+    /// direction = "increasing"
+    /// allow_equal = true
+    /// ```
+    ///
+    /// Missing keys keep the puzzle's values.
+    pub fn from_toml(text: &str) -> Result<SafetyPolicy, String> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e: toml::de::Error| e.message().to_string())?;
+        let mut policy = SafetyPolicy::default();
+
+        for (key, value) in &table {
+            match key.as_str() {
+                "min_step" | "max_step" => {
+                    let step = value
+                        .as_integer()
+                        .and_then(|n| i32::try_from(n).ok())
+                        .filter(|&n| n >= 0)
+                        .ok_or_else(|| format!("`{}` must be a non-negative integer", key))?;
+                    if key == "min_step" {
+                        policy.min_step = step;
+                    } else {
+                        policy.max_step = step;
+                    }
+                }
+                "direction" => {
+                    let direction = value.as_str().ok_or("`direction` must be a string")?;
+                    policy.direction = direction.parse()?;
+                }
+                "allow_equal" => {
+                    policy.allow_equal = value
+                        .as_bool()
+                        .ok_or("`allow_equal` must be true or false")?;
+                }
+                _ => return Err(format!("unknown key `{}`", key)),
+            }
+        }
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Rejects step bounds that no step can satisfy.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_step < 0 || self.min_step > self.max_step {
+            return Err(format!(
+                "the step bounds {}..={} are empty",
+                self.min_step, self.max_step
+            ));
+        }
+        Ok(())
+    }
+
+    // The signs a whole report may move in: `Some(true)` for increasing,
+    // `Some(false)` for decreasing and `None` when each step may go either way
+    pub(crate) fn senses(&self) -> &'static [Option<bool>] {
+        match self.direction {
+            Direction::Increasing => &[Some(true)],
+            Direction::Decreasing => &[Some(false)],
+            Direction::Either => &[Some(true), Some(false)],
+            Direction::NonMonotonic => &[None],
+        }
+    }
+
+    // Whether going from `from` to `to` is a safe step in the given sense
+    pub(crate) fn allows(&self, from: i32, to: i32, sense: Option<bool>) -> bool {
+        let diff = to as i64 - from as i64;
+        if diff == 0 {
+            return self.allow_equal;
+        }
+        let step = match sense {
+            Some(true) => diff,
+            Some(false) => -diff,
+            None => diff.abs(),
+        };
+        (self.min_step as i64..=self.max_step as i64).contains(&step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let policy = SafetyPolicy::from_toml("max_step = 4\ndirection = \"increasing\"").unwrap();
+        assert_eq!(
+            policy,
+            SafetyPolicy {
+                max_step: 4,
+                direction: Direction::Increasing,
+                ..SafetyPolicy::default()
+            }
+        );
+        assert_eq!(SafetyPolicy::from_toml(""), Ok(SafetyPolicy::default()));
+
+        let err = |text| SafetyPolicy::from_toml(text).unwrap_err();
+        assert_eq!(err("max_stpe = 4"), "unknown key `max_stpe`");
+        assert_eq!(err("min_step = 5"), "the step bounds 5..=3 are empty");
+        assert_eq!(
+            err("allow_equal = 1"),
+            "`allow_equal` must be true or false"
+        );
+        assert!(err("direction = \"up\"").starts_with("unknown direction `up`"));
+    }
+
+    #[test]
+    fn test_merge_flags_win() {
+        let file = SafetyPolicy::from_toml(
+            "min_step = 2\nmax_step = 5\ndirection = \"increasing\"\nallow_equal = true",
+        )
+        .unwrap();
+        let merged = |flags| merge(file, flags).unwrap();
+
+        // Nothing given keeps the file as it is
+        assert_eq!(merged(Overrides::default()), file);
+        let flags = Overrides {
+            min_step: Some(1),
+            ..Overrides::default()
+        };
+        assert_eq!(
+            merged(flags),
+            SafetyPolicy {
+                min_step: 1,
+                ..file
+            }
+        );
+        let flags = Overrides {
+            max_step: Some(9),
+            ..Overrides::default()
+        };
+        assert_eq!(
+            merged(flags),
+            SafetyPolicy {
+                max_step: 9,
+                ..file
+            }
+        );
+        let flags = Overrides {
+            direction: Some(Direction::Decreasing),
+            ..Overrides::default()
+        };
+        assert_eq!(
+            merged(flags),
+            SafetyPolicy {
+                direction: Direction::Decreasing,
+                ..file
+            }
+        );
+        // A flag can turn `allow_equal` off as well as on
+        let flags = Overrides {
+            allow_equal: Some(false),
+            ..Overrides::default()
+        };
+        assert_eq!(
+            merged(flags),
+            SafetyPolicy {
+                allow_equal: false,
+                ..file
+            }
+        );
+        let flags = Overrides {
+            allow_equal: Some(true),
+            ..Overrides::default()
+        };
+        assert!(merge(SafetyPolicy::PUZZLE, flags).unwrap().allow_equal);
+    }
+
+    #[test]
+    fn test_merge_checks_the_result() {
+        // Each bound is fine on its own, but not with the other
+        let flags = Overrides {
+            min_step: Some(4),
+            ..Overrides::default()
+        };
+        assert_eq!(
+            merge(SafetyPolicy::PUZZLE, flags),
+            Err("the step bounds 4..=3 are empty".to_string())
+        );
+    }
+
+    #[test]
+    fn test_allows() {
+        let policy = SafetyPolicy::default();
+        assert!(policy.allows(1, 4, Some(true)));
+        assert!(!policy.allows(1, 5, Some(true)));
+        assert!(!policy.allows(4, 1, Some(true)));
+        assert!(!policy.allows(2, 2, Some(true)));
+
+        let policy = SafetyPolicy {
+            allow_equal: true,
+            direction: Direction::NonMonotonic,
+            ..policy
+        };
+        assert!(policy.allows(2, 2, None));
+        assert!(policy.allows(4, 1, None));
+        assert!(policy.allows(i32::MIN, i32::MIN + 1, None));
+    }
+}
@@ -0,0 +1,437 @@
+use std::fmt;
+use std::io::{self, ErrorKind, Read};
+use std::ops::Range;
+
+use crate::lexer::{Call, Scanner, DEFAULT_MAX_DIGITS, MAX_ARGS, MAX_DIGITS};
+use crate::stream::scan_reader;
+
+/// How an instruction combines its arguments into a result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arithmetic {
+    /// The sum of the arguments.
+    Add,
+    /// The first argument minus the rest.
+    Sub,
+    /// The product of the arguments.
+    Mul,
+}
+
+impl Arithmetic {
+    /// The result, or `None` if it overflows.
+    pub fn apply(self, args: &[i64]) -> Option<i64> {
+        match self {
+            Arithmetic::Add => args.iter().try_fold(0i64, |n, &arg| n.checked_add(arg)),
+            Arithmetic::Sub => match args.split_first() {
+                Some((&first, rest)) => rest.iter().try_fold(first, |n, &arg| n.checked_sub(arg)),
+                None => Some(0),
+            },
+            Arithmetic::Mul => args.iter().try_fold(1i64, |n, &arg| n.checked_mul(arg)),
+        }
+    }
+}
+
+/// A counted computation whose result, or the total with it added, does not
+/// fit in an `i64`. Stops the program rather than giving a wrong total.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub name: String,
+    pub args: Vec<i64>,
+    /// Where the call is in memory, in bytes.
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let args: Vec<_> = self.args.iter().map(|n| n.to_string()).collect();
+        write!(
+            f,
+            "`{}({})` at byte {} overflows the total",
+            self.name,
+            args.join(","),
+            self.span.start
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// What running an instruction does to the machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Adds the result to the total, but only while every flag in `when` is
+    /// set.
+    Compute {
+        arithmetic: Arithmetic,
+        when: Vec<String>,
+    },
+    /// Sets a flag on or off.
+    Set { flag: String, value: bool },
+    /// Flips a flag.
+    Toggle { flag: String },
+}
+
+/// A named instruction taking exactly `arity` arguments. Calls with any
+/// other number of arguments are noise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub name: String,
+    pub arity: usize,
+    pub action: Action,
+}
+
+impl Instruction {
+    pub fn compute(name: &str, arity: usize, arithmetic: Arithmetic) -> Instruction {
+        Instruction {
+            name: name.to_string(),
+            arity,
+            action: Action::Compute {
+                arithmetic,
+                when: Vec::new(),
+            },
+        }
+    }
+
+    /// A `name()` that turns `flag` on.
+    pub fn set(name: &str, flag: &str) -> Instruction {
+        Instruction::flag(
+            name,
+            Action::Set {
+                flag: flag.to_string(),
+                value: true,
+            },
+        )
+    }
+
+    /// A `name()` that turns `flag` off.
+    pub fn clear(name: &str, flag: &str) -> Instruction {
+        Instruction::flag(
+            name,
+            Action::Set {
+                flag: flag.to_string(),
+                value: false,
+            },
+        )
+    }
+
+    /// A `name()` that flips `flag`.
+    pub fn toggle(name: &str, flag: &str) -> Instruction {
+        Instruction::flag(
+            name,
+            Action::Toggle {
+                flag: flag.to_string(),
+            },
+        )
+    }
+
+    fn flag(name: &str, action: Action) -> Instruction {
+        Instruction {
+            name: name.to_string(),
+            arity: 0,
+            action,
+        }
+    }
+
+    /// Only counts this computation while `flag` is set.
+    pub fn when(mut self, flag: &str) -> Instruction {
+        match &mut self.action {
+            Action::Compute { when, .. } => when.push(flag.to_string()),
+            _ => panic!("only computations can be conditional"),
+        }
+        self
+    }
+}
+
+/// The instructions a machine understands, the flags it starts with, and
+/// how many digits an argument may have.
+#[derive(Debug, Clone)]
+pub struct InstructionSet {
+    instructions: Vec<Instruction>,
+    // Every flag an instruction mentions, with its starting value
+    flags: Vec<(String, bool)>,
+    max_digits: usize,
+}
+
+/// What a program has done so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    pub total: i64,
+    flags: Vec<(String, bool)>,
+}
+
+impl Machine {
+    pub fn flag(&self, name: &str) -> Option<bool> {
+        self.flags
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|&(_, on)| on)
+    }
+
+    fn flag_mut(&mut self, name: &str) -> &mut bool {
+        let (_, on) = self
+            .flags
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .expect("every flag an instruction mentions is declared");
+        on
+    }
+}
+
+impl Default for InstructionSet {
+    fn default() -> InstructionSet {
+        InstructionSet {
+            instructions: Vec::new(),
+            flags: Vec::new(),
+            max_digits: DEFAULT_MAX_DIGITS,
+        }
+    }
+}
+
+impl InstructionSet {
+    pub fn new() -> InstructionSet {
+        InstructionSet::default()
+    }
+
+    /// Part 1: every `mul(a,b)` counts.
+    pub fn part1() -> InstructionSet {
+        InstructionSet::new().with(Instruction::compute("mul", 2, Arithmetic::Mul))
+    }
+
+    /// Part 2: `mul(a,b)` counts while enabled; `do()` enables it and
+    /// `don't()` disables it.
+    pub fn part2() -> InstructionSet {
+        InstructionSet::new()
+            .with(Instruction::compute("mul", 2, Arithmetic::Mul).when("enabled"))
+            .with(Instruction::set("do", "enabled"))
+            .with(Instruction::clear("don't", "enabled"))
+            .starting("enabled", true)
+    }
+
+    /// Adds an instruction. Flags it mentions start off unless given a
+    /// value with [`InstructionSet::starting`].
+    ///
+    /// Panics if it takes more than [`MAX_ARGS`] arguments, as no call the
+    /// lexer reads could match it.
+    pub fn with(mut self, instruction: Instruction) -> InstructionSet {
+        assert!(
+            instruction.arity <= MAX_ARGS,
+            "`{}` takes {} arguments, but calls have at most {}",
+            instruction.name,
+            instruction.arity,
+            MAX_ARGS
+        );
+        let flags = match &instruction.action {
+            Action::Compute { when, .. } => when.clone(),
+            Action::Set { flag, .. } | Action::Toggle { flag } => vec![flag.clone()],
+        };
+        for flag in flags {
+            if !self.flags.iter().any(|(n, _)| *n == flag) {
+                self.flags.push((flag, false));
+            }
+        }
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn starting(mut self, flag: &str, on: bool) -> InstructionSet {
+        match self.flags.iter_mut().find(|(n, _)| n == flag) {
+            Some((_, value)) => *value = on,
+            None => self.flags.push((flag.to_string(), on)),
+        }
+        self
+    }
+
+    /// Allows arguments of up to `max_digits` digits, rather than the
+    /// puzzle's 3, capped at [`MAX_DIGITS`] (see [`Scanner::max_digits`]).
+    pub fn max_digits(mut self, max_digits: usize) -> InstructionSet {
+        self.max_digits = max_digits.min(MAX_DIGITS);
+        self
+    }
+
+    pub fn machine(&self) -> Machine {
+        Machine {
+            total: 0,
+            flags: self.flags.clone(),
+        }
+    }
+
+    /// Each instruction name once, to scan for.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for instruction in &self.instructions {
+            if !names.contains(&instruction.name.as_str()) {
+                names.push(&instruction.name);
+            }
+        }
+        names
+    }
+
+    /// Runs every instruction found in `memory` on a fresh machine.
+    pub fn run(&self, memory: &[u8]) -> Result<Machine, Overflow> {
+        let names = self.names();
+        let mut machine = self.machine();
+        for call in Scanner::new(memory, &names).max_digits(self.max_digits) {
+            self.execute(&mut machine, names[call.name], &call)?;
+        }
+        Ok(machine)
+    }
+
+    /// Runs every instruction found in memory read from `reader`, a chunk
+    /// at a time (see [`scan_reader`]). An [`Overflow`] comes back as an
+    /// [`ErrorKind::InvalidData`] error.
+    pub fn run_reader<R: Read>(&self, reader: R) -> io::Result<Machine> {
+        let names = self.names();
+        let mut machine = self.machine();
+        scan_reader(reader, &names, self.max_digits, |call| {
+            self.execute(&mut machine, names[call.name], &call)
+                .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+        })?;
+        Ok(machine)
+    }
+
+    /// Runs one call to `name` on `machine`, unless no instruction of that
+    /// name takes its number of arguments.
+    pub fn execute(&self, machine: &mut Machine, name: &str, call: &Call) -> Result<(), Overflow> {
+        let Some(instruction) = self
+            .instructions
+            .iter()
+            .find(|i| i.name == name && i.arity == call.args.len())
+        else {
+            log::trace!("Ignoring {}{:?} at {:?}", name, &*call.args, call.span);
+            return Ok(());
+        };
+        match &instruction.action {
+            Action::Compute { arithmetic, when } => {
+                if !when.iter().all(|flag| machine.flag(flag) == Some(true)) {
+                    log::trace!("Skipping {}{:?} at {:?}", name, &*call.args, call.span);
+                    return Ok(());
+                }
+                let total = arithmetic
+                    .apply(&call.args)
+                    .and_then(|result| machine.total.checked_add(result))
+                    .ok_or_else(|| Overflow {
+                        name: name.to_string(),
+                        args: call.args.to_vec(),
+                        span: call.span.clone(),
+                    })?;
+                log::trace!(
+                    "Adding {} from {} at {:?}",
+                    total - machine.total,
+                    name,
+                    call.span
+                );
+                machine.total = total;
+            }
+            Action::Set { flag, value } => {
+                *machine.flag_mut(flag) = *value;
+                log::debug!(
+                    "Found {}() at {:?}, setting {} to {}",
+                    name,
+                    call.span,
+                    flag,
+                    value
+                );
+            }
+            Action::Toggle { flag } => {
+                let on = machine.flag_mut(flag);
+                *on = !*on;
+                log::debug!(
+                    "Found {}() at {:?}, setting {} to {}",
+                    name,
+                    call.span,
+                    flag,
+                    on
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &[u8] =
+        b"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_puzzle_instruction_sets() {
+        assert_eq!(InstructionSet::part1().run(EXAMPLE).unwrap().total, 161);
+        let machine = InstructionSet::part2().run(EXAMPLE).unwrap();
+        assert_eq!(machine.total, 48);
+        assert_eq!(machine.flag("enabled"), Some(true));
+        assert_eq!(
+            InstructionSet::part2().run_reader(EXAMPLE).unwrap(),
+            machine
+        );
+    }
+
+    #[test]
+    fn test_other_instructions() {
+        let set = InstructionSet::new()
+            .with(Instruction::compute("add", 2, Arithmetic::Add))
+            .with(Instruction::compute("add", 3, Arithmetic::Add))
+            .with(Instruction::compute("sub", 2, Arithmetic::Sub).when("neg"))
+            .with(Instruction::toggle("flip", "neg"));
+        // add(4) has the wrong arity; sub only counts between the flips
+        let memory = b"add(1,2)add(4)sub(9,1)flip()sub(1,9)add(1,1,1)flip()sub(5,5)";
+        let machine = set.run(memory).unwrap();
+        assert_eq!(machine.total, 3 - 8 + 3);
+        assert_eq!(machine.flag("neg"), Some(false));
+        assert_eq!(machine.flag("other"), None);
+    }
+
+    #[test]
+    fn test_max_arity() {
+        let set =
+            InstructionSet::new().with(Instruction::compute("add", MAX_ARGS, Arithmetic::Add));
+        assert_eq!(set.run(b"add(1,2,3,4)").unwrap().total, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "`add` takes 5 arguments, but calls have at most 4")]
+    fn test_arity_above_max_args_is_rejected() {
+        InstructionSet::new().with(Instruction::compute("add", MAX_ARGS + 1, Arithmetic::Add));
+    }
+
+    #[test]
+    fn test_every_guard_must_be_set() {
+        let set = InstructionSet::new()
+            .with(
+                Instruction::compute("mul", 2, Arithmetic::Mul)
+                    .when("a")
+                    .when("b"),
+            )
+            .with(Instruction::set("a", "a"))
+            .with(Instruction::set("b", "b"))
+            .with(Instruction::clear("na", "a"));
+        let machine = set
+            .run(b"mul(2,2)a()mul(3,3)b()mul(4,4)na()mul(5,5)")
+            .unwrap();
+        assert_eq!(machine.total, 16);
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        // Each product fits in an i64, but not two of them
+        let set = InstructionSet::part2().max_digits(10);
+        let memory = b"mul(3037000499,3037000499)mul(1,1)mul(3037000499,3037000499)";
+        let err = set.run(memory).unwrap_err();
+        assert_eq!(err.span, 34..60);
+        assert_eq!(
+            err.to_string(),
+            "`mul(3037000499,3037000499)` at byte 34 overflows the total"
+        );
+        let err = set.run_reader(&memory[..]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        // Calls that are skipped never count, so never overflow
+        let memory = b"mul(3037000499,3037000499)don't()mul(9999999999,9999999999)";
+        assert_eq!(set.run(memory).unwrap().total, 3037000499 * 3037000499);
+
+        let product = Arithmetic::Mul.apply(&[i64::MAX, 2]);
+        assert_eq!(product, None);
+        assert_eq!(Arithmetic::Sub.apply(&[1, i64::MIN]), None);
+        assert_eq!(Arithmetic::Sub.apply(&[1, 2, 3]), Some(-4));
+    }
+}
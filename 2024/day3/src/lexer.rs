@@ -0,0 +1,377 @@
+use std::ops::{Deref, Range};
+
+/// The most arguments a call may have; longer lists are noise.
+pub const MAX_ARGS: usize = 4;
+
+/// The most digits an argument may have unless told otherwise: the puzzle
+/// says 1 to 3.
+pub const DEFAULT_MAX_DIGITS: usize = 3;
+
+/// The most digits an argument may ever have: those of `i64::MAX`.
+pub const MAX_DIGITS: usize = 19;
+
+/// What a token in corrupted memory says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// `mul(a,b)`
+    Mul(i64, i64),
+    /// `do()`
+    Do,
+    /// `don't()`
+    Dont,
+}
+
+/// A well-formed instruction and the bytes of the input it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Range<usize>,
+}
+
+/// A call such as `add(1,2,3)`: one of the names the [`Scanner`] looks for
+/// (as an index into them), its arguments and the bytes it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub name: usize,
+    pub args: Args,
+    pub span: Range<usize>,
+}
+
+/// A call's arguments, stored inline so scanning never allocates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Args {
+    values: [i64; MAX_ARGS],
+    len: usize,
+}
+
+impl Deref for Args {
+    type Target = [i64];
+
+    fn deref(&self) -> &[i64] {
+        &self.values[..self.len]
+    }
+}
+
+/// Finds calls to the given names in corrupted memory, skipping everything
+/// else.
+///
+/// A call is a name, `(`, up to [`MAX_ARGS`] comma-separated runs of 1 to
+/// [`DEFAULT_MAX_DIGITS`] digits (see [`Scanner::max_digits`]) and `)`, with
+/// nothing in between. Works on bytes and never allocates per
+/// call. A candidate that turns out to be malformed (`mul(4*`,
+/// `mul ( 2 , 4 )`) is noise, and scanning resumes one byte after where it
+/// started, so `mul(mul(2,3)` still finds `mul(2,3)`.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a, 'n> {
+    bytes: &'a [u8],
+    pos: usize,
+    names: &'n [&'n str],
+    // Whether any name starts with each byte
+    starts: [bool; 256],
+    max_digits: usize,
+    // Where `bytes` starts in the whole input
+    offset: usize,
+    // Whether more input follows `bytes`, so a call may be cut off at the end
+    more: bool,
+    // Set once a call runs off the end of `bytes`; scanning stops at `pos`
+    cut: bool,
+}
+
+// What starts at a position in the input
+enum Candidate {
+    Call(Call),
+    Noise,
+    // Could still be a call, but runs off the end of what has been read
+    Cut,
+}
+
+impl<'a, 'n> Scanner<'a, 'n> {
+    pub fn new(bytes: &'a [u8], names: &'n [&'n str]) -> Scanner<'a, 'n> {
+        let mut starts = [false; 256];
+        for name in names {
+            if let Some(&first) = name.as_bytes().first() {
+                starts[first as usize] = true;
+            }
+        }
+        Scanner {
+            bytes,
+            pos: 0,
+            names,
+            starts,
+            max_digits: DEFAULT_MAX_DIGITS,
+            offset: 0,
+            more: false,
+            cut: false,
+        }
+    }
+
+    /// Allows arguments of up to `max_digits` digits, capped at
+    /// [`MAX_DIGITS`]; longer runs, leading zeros included, make a call
+    /// noise. Values must still fit in an `i64`.
+    pub fn max_digits(mut self, max_digits: usize) -> Scanner<'a, 'n> {
+        self.max_digits = max_digits.min(MAX_DIGITS);
+        self
+    }
+
+    /// Scans `bytes` found at `offset` in a longer input. If `more` input
+    /// follows them, stops at the first call that may be cut off, which
+    /// [`Scanner::resume_at`] then points at.
+    pub(crate) fn chunk(
+        bytes: &'a [u8],
+        names: &'n [&'n str],
+        max_digits: usize,
+        offset: usize,
+        more: bool,
+    ) -> Scanner<'a, 'n> {
+        Scanner {
+            offset,
+            more,
+            ..Scanner::new(bytes, names).max_digits(max_digits)
+        }
+    }
+
+    /// Where in `bytes` scanning has got to: the start of a cut off call, or
+    /// the end once every call has been found.
+    pub(crate) fn resume_at(&self) -> usize {
+        self.pos
+    }
+
+    fn candidate_at(&self, start: usize) -> Candidate {
+        let rest = &self.bytes[start..];
+        let name = self.names.iter().position(|name| {
+            rest.starts_with(name.as_bytes()) && rest.get(name.len()) == Some(&b'(')
+        });
+        let Some(name) = name else {
+            // `mu` at the end of a chunk may yet be `mul(`
+            let cut = self
+                .names
+                .iter()
+                .any(|name| name.as_bytes().starts_with(rest));
+            return if self.more && cut {
+                Candidate::Cut
+            } else {
+                Candidate::Noise
+            };
+        };
+
+        let mut at = start + self.names[name].len() + 1;
+        let mut args = Args {
+            values: [0; MAX_ARGS],
+            len: 0,
+        };
+        loop {
+            match self.bytes.get(at) {
+                Some(b')') if args.len == 0 => {
+                    at += 1;
+                    break;
+                }
+                Some(b) if b.is_ascii_digit() => {}
+                Some(_) => return Candidate::Noise,
+                None => return self.cut_off(),
+            }
+            let digits = self.bytes[at..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .take(self.max_digits.saturating_add(1))
+                .count();
+            if digits > self.max_digits {
+                return Candidate::Noise;
+            }
+            let Some(n) = parse_digits(&self.bytes[at..at + digits]) else {
+                return Candidate::Noise;
+            };
+            if args.len == MAX_ARGS {
+                return Candidate::Noise;
+            }
+            args.values[args.len] = n;
+            args.len += 1;
+            at += digits;
+            match self.bytes.get(at) {
+                Some(b',') => at += 1,
+                Some(b')') => {
+                    at += 1;
+                    break;
+                }
+                Some(_) => return Candidate::Noise,
+                None => return self.cut_off(),
+            }
+        }
+        Candidate::Call(Call {
+            name,
+            args,
+            span: self.offset + start..self.offset + at,
+        })
+    }
+
+    fn cut_off(&self) -> Candidate {
+        if self.more {
+            Candidate::Cut
+        } else {
+            Candidate::Noise
+        }
+    }
+}
+
+// The value of a run of digits, if it fits
+fn parse_digits(digits: &[u8]) -> Option<i64> {
+    digits.iter().try_fold(0i64, |n, b| {
+        n.checked_mul(10)?.checked_add((b - b'0') as i64)
+    })
+}
+
+impl Iterator for Scanner<'_, '_> {
+    type Item = Call;
+
+    fn next(&mut self) -> Option<Call> {
+        while !self.cut && self.pos < self.bytes.len() {
+            let start = self.pos;
+            if !self.starts[self.bytes[start] as usize] {
+                self.pos += 1;
+                continue;
+            }
+            match self.candidate_at(start) {
+                Candidate::Call(call) => {
+                    self.pos = call.span.end - self.offset;
+                    return Some(call);
+                }
+                Candidate::Noise => self.pos += 1,
+                Candidate::Cut => self.cut = true,
+            }
+        }
+        None
+    }
+}
+
+const NAMES: &[&str] = &["mul", "do", "don't"];
+
+/// Finds the puzzle's instructions in corrupted memory: a [`Scanner`] for
+/// `mul`, `do` and `don't` that drops calls with the wrong number of
+/// arguments.
+#[derive(Debug, Clone)]
+pub struct Lexer<'a> {
+    scanner: Scanner<'a, 'static>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Lexer<'a> {
+        Lexer::from_bytes(input.as_bytes())
+    }
+
+    /// Memory need not be text: anything that isn't an instruction is noise.
+    pub fn from_bytes(bytes: &'a [u8]) -> Lexer<'a> {
+        Lexer {
+            scanner: Scanner::new(bytes, NAMES),
+        }
+    }
+
+    /// See [`Scanner::max_digits`].
+    pub fn max_digits(self, max_digits: usize) -> Lexer<'a> {
+        Lexer {
+            scanner: self.scanner.max_digits(max_digits),
+        }
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        self.scanner.find_map(|call| {
+            let kind = match (NAMES[call.name], &*call.args) {
+                ("mul", &[a, b]) => TokenKind::Mul(a, b),
+                ("do", []) => TokenKind::Do,
+                ("don't", []) => TokenKind::Dont,
+                _ => return None,
+            };
+            Some(Token {
+                kind,
+                span: call.span,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<(TokenKind, &str)> {
+        Lexer::new(input)
+            .map(|token| (token.kind, &input[token.span]))
+            .collect()
+    }
+
+    #[test]
+    fn test_example_tokens() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(
+            tokens(input),
+            [
+                (TokenKind::Mul(2, 4), "mul(2,4)"),
+                (TokenKind::Dont, "don't()"),
+                (TokenKind::Mul(5, 5), "mul(5,5)"),
+                (TokenKind::Mul(11, 8), "mul(11,8)"),
+                (TokenKind::Do, "do()"),
+                (TokenKind::Mul(8, 5), "mul(8,5)"),
+            ]
+        );
+        let spans: Vec<_> = Lexer::new(input).map(|token| token.span).collect();
+        assert_eq!(spans[0], 1..9);
+    }
+
+    #[test]
+    fn test_malformed_candidates_are_noise() {
+        assert_eq!(
+            tokens("mul(4*, mul(6,9!, ?(12,34), mul ( 2 , 4 ), mul(,2), do(), "),
+            [(TokenKind::Do, "do()")]
+        );
+        assert_eq!(tokens("mul(mul(2,3)"), [(TokenKind::Mul(2, 3), "mul(2,3)")]);
+        assert_eq!(tokens("don'tdo()"), [(TokenKind::Do, "do()")]);
+        // Nor are operands longer than three digits
+        assert_eq!(tokens("mul(1234,5)mul(0012,5)mul(1,2"), []);
+        // Nor are calls with the wrong number of them
+        assert_eq!(tokens("mul(1,2,3)mul(4)do(5)mul()"), []);
+    }
+
+    #[test]
+    fn test_digit_limit() {
+        let input = "mul(1234,5)mul(0012,5)mul(99999999999999999999,1)";
+        let kinds: Vec<_> = Lexer::new(input).max_digits(4).map(|t| t.kind).collect();
+        assert_eq!(kinds, [TokenKind::Mul(1234, 5), TokenKind::Mul(12, 5)]);
+        // Whatever the limit, operands have to fit
+        let kinds: Vec<_> = Lexer::new(input).max_digits(40).map(|t| t.kind).collect();
+        assert_eq!(kinds.len(), 2);
+        // Nor can the limit pass the digits of `i64::MAX`
+        let input = "mul(9223372036854775807,1)mul(10000000000000000000,1)";
+        let kinds: Vec<_> = Lexer::new(input)
+            .max_digits(usize::MAX)
+            .map(|t| t.kind)
+            .collect();
+        assert_eq!(kinds, [TokenKind::Mul(i64::MAX, 1)]);
+    }
+
+    #[test]
+    fn test_non_text_memory() {
+        let bytes = b"\xff\xfemul(3,4)\x00do()";
+        let kinds: Vec<_> = Lexer::from_bytes(bytes).map(|t| t.kind).collect();
+        assert_eq!(kinds, [TokenKind::Mul(3, 4), TokenKind::Do]);
+    }
+
+    #[test]
+    fn test_scanner_finds_any_names() {
+        let names = &["add", "addx", "flip"];
+        let input = b"add(1,2,3)addx(7)flip()add()add(1,2,3,4,5)add(1,)";
+        let calls: Vec<_> = Scanner::new(input, names)
+            .map(|call| (names[call.name], call.args.to_vec()))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                ("add", vec![1, 2, 3]),
+                ("addx", vec![7]),
+                ("flip", vec![]),
+                ("add", vec![]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use aoc_core::{Answer, ParseError, Solution};
+
+mod interpreter;
+mod lexer;
+mod stream;
+
+pub use interpreter::{Action, Arithmetic, Instruction, InstructionSet, Machine, Overflow};
+pub use lexer::{
+    Args, Call, Lexer, Scanner, Token, TokenKind, DEFAULT_MAX_DIGITS, MAX_ARGS, MAX_DIGITS,
+};
+pub use stream::{scan_reader, Unwrapped, CHUNK_SIZE};
+
+pub struct Day3;
+
+fn scan(input: &str) -> Result<(i64, i64), ParseError> {
+    let memory = input.as_bytes();
+    let run = |set: InstructionSet| {
+        set.run(memory).map(|machine| machine.total).map_err(|e| {
+            let before = &input[..e.span.start];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let line = before.matches('\n').count() + 1;
+            let column = before[line_start..].chars().count() + 1;
+            ParseError::at(line, column, e.to_string())
+        })
+    };
+    Ok((run(InstructionSet::part1())?, run(InstructionSet::part2())?))
+}
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
+
+    // Both parts' programs run while parsing, so there is nothing left to solve
+    type Input = (i64, i64);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        // Corrupted memory is the puzzle, so any text is valid input as long
+        // as the totals fit
+        scan(input)
+    }
+
+    fn part1(&(total, _): &Self::Input) -> Answer {
+        total.into()
+    }
+
+    fn part2(&(_, total2): &Self::Input) -> Answer {
+        total2.into()
+    }
+}
@@ -1,92 +1,121 @@
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-fn parse_number(chars: &[char], start: &mut usize) -> Option<i32> {
-    let mut num = String::new();
-    while *start < chars.len() && chars[*start].is_digit(10) {
-        num.push(chars[*start]);
-        *start += 1;
-    }
-    num.parse().ok()
-}
+use day3::{scan_reader, InstructionSet, Unwrapped, DEFAULT_MAX_DIGITS, MAX_DIGITS};
 
-fn extract_multiplication(chars: &[char], pos: &mut usize) -> Option<i32> {
-    // Check for "mul("
-    if *pos + 4 > chars.len() {
-        return None;
-    }
-    if chars[*pos..*pos + 4].iter().collect::<String>() != "mul(" {
-        return None;
-    }
-    *pos += 4; // Skip "mul("
-    
-    // Parse first number
-    let num1 = parse_number(chars, pos)?;
-    
-    // Check for comma
-    if *pos >= chars.len() || chars[*pos] != ',' {
-        return None;
-    }
-    *pos += 1;
-    
-    // Parse second number
-    let num2 = parse_number(chars, pos)?;
-    
-    // Check for closing parenthesis
-    if *pos >= chars.len() || chars[*pos] != ')' {
-        return None;
-    }
-    *pos += 1;
-    
-    Some(num1 * num2)
+const USAGE: &str = "\
+Usage:
+    day3 [--unwrap] [--max-digits <n>] [<path> | -]
+
+Prints the part 1 and part 2 totals of the memory in <path>, stdin (`-`) or
+this day's input.txt. Memory is streamed, so it may be any size.
+
+    --unwrap            ignore line breaks, for memory that was wrapped
+                        onto lines
+    --max-digits <n>    longest argument, in digits (default 3, at most
+                        19); a total that overflows 64 bits is an error";
+
+#[derive(Debug)]
+struct Options {
+    unwrap: bool,
+    max_digits: usize,
+    input: Option<PathBuf>,
 }
 
-fn extract_multiplications(line: &str, total: &mut i32, total2: &mut i32, enabled: &mut bool) {
-    let chars: Vec<char> = line.chars().collect();
-    let mut pos = 0;
-    
-    while pos < chars.len() {
-        // Check for "don't()" (7 characters)
-        if pos + 7 <= chars.len() && chars[pos..pos + 7].iter().collect::<String>() == "don't()" {
-            *enabled = false;
-            println!("Found don't() at position {}, setting enabled to false", pos);
-            pos += 7;
-        }
-        // Check for "do()" (4 characters)
-        else if pos + 4 <= chars.len() && chars[pos..pos + 4].iter().collect::<String>() == "do()" {
-            *enabled = true;
-            println!("Found do() at position {}, setting enabled to true", pos);
-            pos += 4;
-        }
-        // Check for "mul(x,y)"
-        else if let Some(result) = extract_multiplication(&chars, &mut pos) {
-            *total += result;
-            if *enabled {
-                *total2 += result;
-                println!("Adding {} to total2 (enabled)", result);
-            } else {
-                println!("Skipping {} as enabled is false", result);
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut unwrap = false;
+    let mut max_digits = DEFAULT_MAX_DIGITS;
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--unwrap" => unwrap = true,
+            "--max-digits" => {
+                let n = args.next().ok_or("--max-digits needs a value")?;
+                max_digits = n
+                    .parse()
+                    .ok()
+                    .filter(|&n| (1..=MAX_DIGITS).contains(&n))
+                    .ok_or_else(|| {
+                        format!("invalid digit count `{}`, expected 1 to {}", n, MAX_DIGITS)
+                    })?;
             }
-            println!("Current totals -> total: {}, total2: {}", total, total2);
-        } else {
-            pos += 1;
+            _ if arg.starts_with("--") => return Err(format!("unknown flag `{}`", arg)),
+            _ if input.is_none() => input = Some(arg),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
         }
     }
+
+    let input = match input.as_deref() {
+        Some("-") => None,
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("input.txt")),
+    };
+    Ok(Options {
+        unwrap,
+        max_digits,
+        input,
+    })
 }
 
-fn main() -> io::Result<()> {
-    let file = File::open("3.txt")?;
-    let reader = BufReader::new(file);
-    let mut total = 0;
-    let mut total2 = 0;
-    let mut enabled = true;
+fn run(options: &Options) -> Result<(), String> {
+    let (name, reader): (String, Box<dyn Read>) = match &options.input {
+        Some(path) => {
+            let file =
+                File::open(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+            (path.display().to_string(), Box::new(file))
+        }
+        None => ("stdin".to_string(), Box::new(io::stdin().lock())),
+    };
+    let reader = if options.unwrap {
+        Box::new(Unwrapped::new(reader))
+    } else {
+        reader
+    };
 
-    for line in reader.lines() {
-        let line = line?;
-        extract_multiplications(&line, &mut total, &mut total2, &mut enabled);
+    // Both parts' programs run on a single pass over the memory
+    let sets = [InstructionSet::part1(), InstructionSet::part2()]
+        .map(|set| set.max_digits(options.max_digits));
+    let mut names = Vec::new();
+    for name in sets.iter().flat_map(|set| set.names()) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
     }
+    let mut machines = sets.each_ref().map(|set| set.machine());
+    scan_reader(reader, &names, options.max_digits, |call| {
+        for (set, machine) in sets.iter().zip(&mut machines) {
+            set.execute(machine, names[call.name], &call)
+                .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+        }
+        Ok(())
+    })
+    .map_err(|e| match e.kind() {
+        ErrorKind::InvalidData => format!("{}: {}", name, e),
+        _ => format!("cannot read {}: {}", name, e),
+    })?;
 
-    println!("Total sum of all multiplications: {}", total);
-    println!("Total sum of enabled multiplications: {}", total2);
+    for machine in &machines {
+        println!("{}", machine.total);
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use std::io::{self, ErrorKind, Read};
+
+use crate::lexer::{Call, Scanner};
+
+/// How much memory is read at a time.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Finds the calls to `names` (with arguments of up to `max_digits` digits)
+/// in memory read from `reader`, passing each to `found` in order, with
+/// spans counted from the start of the stream. Stops at the first error,
+/// from reading or from `found`.
+///
+/// Memory is read a chunk at a time rather than a line at a time, and a call
+/// cut off at the end of a chunk is finished from the next, so reads split
+/// nothing. Line breaks are part of memory, as in the puzzle; wrap the
+/// reader in [`Unwrapped`] if they were added after the fact. Only one chunk
+/// is held, plus the start of a call cut off at its end; the buffer grows
+/// only if a single call could be longer than a whole chunk, which takes a
+/// very long name or digit limit.
+pub fn scan_reader<R: Read>(
+    reader: R,
+    names: &[&str],
+    max_digits: usize,
+    found: impl FnMut(Call) -> io::Result<()>,
+) -> io::Result<()> {
+    scan_chunks(reader, names, max_digits, CHUNK_SIZE, found)
+}
+
+/// Reads memory that was wrapped onto several lines as one long line,
+/// dropping every `\n` and `\r`.
+#[derive(Debug)]
+pub struct Unwrapped<R> {
+    inner: R,
+}
+
+impl<R: Read> Unwrapped<R> {
+    pub fn new(inner: R) -> Unwrapped<R> {
+        Unwrapped { inner }
+    }
+}
+
+impl<R: Read> Read for Unwrapped<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read = self.inner.read(buf)?;
+            if read == 0 {
+                return Ok(0);
+            }
+            let mut kept = 0;
+            for i in 0..read {
+                if !matches!(buf[i], b'\n' | b'\r') {
+                    buf[kept] = buf[i];
+                    kept += 1;
+                }
+            }
+            // Zero would mean the end of the stream, so read on past blank lines
+            if kept > 0 {
+                return Ok(kept);
+            }
+        }
+    }
+}
+
+pub(crate) fn scan_chunks<R: Read>(
+    mut reader: R,
+    names: &[&str],
+    max_digits: usize,
+    chunk_size: usize,
+    mut found: impl FnMut(Call) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut buffer = vec![0; chunk_size];
+    // Bytes held, and where the first of them sits in the stream
+    let mut len = 0;
+    let mut offset = 0;
+
+    loop {
+        if len == buffer.len() {
+            buffer.resize(2 * len, 0);
+        }
+        let read = match reader.read(&mut buffer[len..]) {
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        len += read;
+
+        let more = read > 0;
+        let mut scanner = Scanner::chunk(&buffer[..len], names, max_digits, offset, more);
+        scanner.by_ref().try_for_each(&mut found)?;
+        if !more {
+            return Ok(());
+        }
+        let keep = scanner.resume_at();
+        buffer.copy_within(keep..len, 0);
+        len -= keep;
+        offset += keep;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{DEFAULT_MAX_DIGITS, MAX_DIGITS};
+
+    const NAMES: &[&str] = &["mul", "do", "don't"];
+
+    // Delivers its bytes a few at a time, like a slow pipe
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.bytes.len());
+            buf[..n].copy_from_slice(&self.bytes[..n]);
+            self.bytes = &self.bytes[n..];
+            Ok(n)
+        }
+    }
+
+    fn calls(
+        memory: &[u8],
+        step: usize,
+        max_digits: usize,
+        chunk_size: usize,
+    ) -> Vec<(String, Vec<i64>, usize)> {
+        let mut calls = Vec::new();
+        let reader = Trickle {
+            bytes: memory,
+            step,
+        };
+        scan_chunks(reader, NAMES, max_digits, chunk_size, |call| {
+            calls.push((
+                NAMES[call.name].to_string(),
+                call.args.to_vec(),
+                call.span.start,
+            ));
+            Ok(())
+        })
+        .unwrap();
+        calls
+    }
+
+    #[test]
+    fn test_calls_across_chunks() {
+        let memory = b"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let whole: Vec<_> = Scanner::new(memory, NAMES)
+            .map(|call| {
+                (
+                    NAMES[call.name].to_string(),
+                    call.args.to_vec(),
+                    call.span.start,
+                )
+            })
+            .collect();
+        assert_eq!(whole.len(), 6);
+        for step in 1..memory.len() {
+            for chunk_size in [1, 2, 3, 7, 64] {
+                assert_eq!(
+                    calls(memory, step, DEFAULT_MAX_DIGITS, chunk_size),
+                    whole,
+                    "{} {}",
+                    step,
+                    chunk_size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_breaks_split_calls() {
+        // A newline inside a call breaks it; one between calls does not matter
+        let memory = b"mul(1,\n2)do()\nmul(3,4)mu\nl(5,6)";
+        let found: Vec<_> = calls(memory, 4, DEFAULT_MAX_DIGITS, 4)
+            .into_iter()
+            .map(|(name, ..)| name)
+            .collect();
+        assert_eq!(found, ["do", "mul"]);
+
+        let reader = Unwrapped::new(&memory[..]);
+        let mut found = Vec::new();
+        scan_chunks(reader, NAMES, DEFAULT_MAX_DIGITS, 4, |call| {
+            found.push(call.args.to_vec());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(found, [vec![1, 2], vec![], vec![3, 4], vec![5, 6]]);
+    }
+
+    #[test]
+    fn test_errors_stop_the_scan() {
+        let mut seen = 0;
+        let err = scan_reader(&b"mul(1,2)mul(3,4)mul(5,6)"[..], NAMES, 3, |call| {
+            seen += 1;
+            match *call.args {
+                [3, 4] => Err(io::Error::new(ErrorKind::InvalidData, "no")),
+                _ => Ok(()),
+            }
+        });
+        assert_eq!(err.unwrap_err().to_string(), "no");
+        assert_eq!(seen, 2);
+    }
+
+    #[test]
+    fn test_long_candidates_grow_the_buffer() {
+        let zeros = "0".repeat(18);
+        let memory = format!("mul({0}7,{0}6)mul(1,2,3,4,5{0})", zeros);
+        let found = calls(memory.as_bytes(), 16, MAX_DIGITS, 8);
+        assert_eq!(found, [("mul".to_string(), vec![7, 6], 0)]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::{HashMap, VecDeque};
+
+const ROOT: usize = 0;
+
+/// An Aho-Corasick automaton: finds every occurrence of any of a set of
+/// words in one pass over a sequence of characters, overlapping ones
+/// included.
+///
+/// Building takes time linear in the total length of the words (times the
+/// size of their alphabet), and scanning one step per character plus one per
+/// match, however many words there are.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    // Each distinct character in the words, numbered; others lead to the root
+    alphabet: HashMap<char, usize>,
+    // `next[state * alphabet.len() + symbol]`, with failures already followed
+    next: Vec<usize>,
+    // The words (as indices) ending at each state, and the nearest proper
+    // suffix state where one ends
+    ends: Vec<Vec<usize>>,
+    dict: Vec<Option<usize>>,
+    lens: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Builds the automaton for `words`. Matches are reported by index into
+    /// `words`; an empty word never matches.
+    pub fn new(words: &[&str]) -> AhoCorasick {
+        let mut alphabet = HashMap::new();
+        for c in words.iter().flat_map(|word| word.chars()) {
+            let symbol = alphabet.len();
+            alphabet.entry(c).or_insert(symbol);
+        }
+        let width = alphabet.len();
+
+        // The trie, with `ROOT` marking a missing edge (nothing points back
+        // at the root)
+        let mut next = vec![ROOT; width];
+        let mut ends = vec![Vec::new()];
+        for (index, word) in words.iter().enumerate() {
+            if word.is_empty() {
+                continue;
+            }
+            let mut state = ROOT;
+            for c in word.chars() {
+                let edge = state * width + alphabet[&c];
+                if next[edge] == ROOT {
+                    next[edge] = ends.len();
+                    next.extend(std::iter::repeat_n(ROOT, width));
+                    ends.push(Vec::new());
+                }
+                state = next[edge];
+            }
+            ends[state].push(index);
+        }
+
+        // Breadth first, every state's failure is settled before its
+        // children need it, so missing edges can borrow the failure's
+        let states = ends.len();
+        let mut fail = vec![ROOT; states];
+        let mut dict = vec![None; states];
+        let mut queue: VecDeque<usize> = next[..width]
+            .iter()
+            .copied()
+            .filter(|&child| child != ROOT)
+            .collect();
+        while let Some(state) = queue.pop_front() {
+            for symbol in 0..width {
+                let edge = state * width + symbol;
+                let fallback = next[fail[state] * width + symbol];
+                let child = next[edge];
+                if child == ROOT {
+                    next[edge] = fallback;
+                } else {
+                    fail[child] = fallback;
+                    dict[child] = if ends[fallback].is_empty() {
+                        dict[fallback]
+                    } else {
+                        Some(fallback)
+                    };
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        AhoCorasick {
+            alphabet,
+            next,
+            ends,
+            dict,
+            lens: words.iter().map(|word| word.chars().count()).collect(),
+        }
+    }
+
+    /// How many words the automaton was built for.
+    pub fn words(&self) -> usize {
+        self.lens.len()
+    }
+
+    /// The length of word `index` in characters.
+    pub fn word_len(&self, index: usize) -> usize {
+        self.lens[index]
+    }
+
+    /// Scans `chars`, calling `found(word, end)` for every occurrence of a
+    /// word, where `end` is the position just past its last character.
+    pub fn scan<I, F>(&self, chars: I, mut found: F)
+    where
+        I: IntoIterator<Item = char>,
+        F: FnMut(usize, usize),
+    {
+        let width = self.alphabet.len();
+        let mut state = ROOT;
+        for (i, c) in chars.into_iter().enumerate() {
+            state = match self.alphabet.get(&c) {
+                Some(&symbol) => self.next[state * width + symbol],
+                None => ROOT,
+            };
+            let mut at = Some(state);
+            while let Some(s) = at {
+                for &word in &self.ends[s] {
+                    found(word, i + 1);
+                }
+                at = self.dict[s];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(words: &[&str], text: &str) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        AhoCorasick::new(words).scan(text.chars(), |word, end| found.push((word, end)));
+        found
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        assert_eq!(
+            matches(&["he", "she", "his", "hers"], "ushers"),
+            [(1, 4), (0, 4), (3, 6)]
+        );
+        assert_eq!(matches(&["aa"], "aaaa"), [(0, 2), (0, 3), (0, 4)]);
+    }
+
+    #[test]
+    fn test_duplicate_and_empty_words() {
+        assert_eq!(
+            matches(&["ab", "", "ab", "b"], "xab"),
+            [(0, 3), (2, 3), (3, 3)]
+        );
+        assert_eq!(matches(&[], "abc"), []);
+    }
+
+    #[test]
+    fn test_unknown_characters_reset() {
+        assert_eq!(matches(&["XMAS"], "XMA.SXMAS"), [(0, 9)]);
+        assert_eq!(matches(&["ÅÄ"], "ÅÅÄ"), [(0, 3)]);
+    }
+}
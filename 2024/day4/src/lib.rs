@@ -0,0 +1,40 @@
+use aoc_core::{Answer, ParseError, Solution};
+use grid::Grid;
+
+mod automaton;
+mod search;
+mod template;
+
+pub use automaton::AhoCorasick;
+pub use search::{search_words, Match, WordMatches};
+pub use template::{Placement, Template, WILDCARD};
+
+pub struct Day4;
+
+fn read_matrix(input: &str) -> Result<Grid<char>, ParseError> {
+    Ok(Grid::parse(input)?)
+}
+
+/// The puzzle's X-MAS: two `MAS` crossing on their `A`, each either way
+/// round.
+pub const X_MAS: &str = "M.S / .A. / M.S";
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 4;
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_matrix(input)
+    }
+
+    fn part1(matrix: &Self::Input) -> Answer {
+        search_words(matrix, &["XMAS"])[0].count().into()
+    }
+
+    fn part2(matrix: &Self::Input) -> Answer {
+        let template: Template = X_MAS.parse().expect("X_MAS is a valid template");
+        template.count(matrix).into()
+    }
+}
@@ -0,0 +1,151 @@
+use grid::{Grid, Point};
+
+use crate::automaton::AhoCorasick;
+
+/// The four line directions; each line is read both ways, covering all eight.
+const FAMILIES: [Point; 4] = [
+    Point::RIGHT,
+    Point::DOWN,
+    Point::DOWN_RIGHT,
+    Point::DOWN_LEFT,
+];
+
+/// Where a word was found: the cell of its first letter and the direction it
+/// reads in, one of [`Point::ALL_DIRECTIONS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Match {
+    pub start: Point,
+    pub direction: Point,
+}
+
+/// Every place one word was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordMatches {
+    pub word: String,
+    pub matches: Vec<Match>,
+}
+
+impl WordMatches {
+    pub fn count(&self) -> usize {
+        self.matches.len()
+    }
+}
+
+/// Finds every occurrence of each of `words` in `grid`, reading in all eight
+/// directions, and returns them per word in the order given.
+///
+/// Every row, column and diagonal is read once each way through one
+/// [`AhoCorasick`] automaton for all the words, so the search is linear in
+/// the size of the grid (plus the matches) however large the dictionary.
+/// Overlapping and crossing matches all count. A one-letter word is found
+/// once per direction; an empty word is never found.
+pub fn search_words(grid: &Grid<char>, words: &[&str]) -> Vec<WordMatches> {
+    let automaton = AhoCorasick::new(words);
+    let mut found: Vec<WordMatches> = words
+        .iter()
+        .map(|word| WordMatches {
+            word: word.to_string(),
+            matches: Vec::new(),
+        })
+        .collect();
+
+    let mut line = Vec::new();
+    for step in FAMILIES {
+        for cells in grid.lines(step) {
+            line.clear();
+            line.extend(cells.map(|(p, &c)| (p, c)));
+
+            automaton.scan(line.iter().map(|&(_, c)| c), |word, end| {
+                let start = end - automaton.word_len(word);
+                found[word].matches.push(Match {
+                    start: line[start].0,
+                    direction: step,
+                });
+            });
+            // Read backwards, a match ending at `end` starts `end` cells
+            // from the far end of the line
+            automaton.scan(line.iter().rev().map(|&(_, c)| c), |word, end| {
+                let start = end - automaton.word_len(word);
+                found[word].matches.push(Match {
+                    start: line[line.len() - 1 - start].0,
+                    direction: -step,
+                });
+            });
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    // Tries every word from every cell in every direction
+    fn brute_force(grid: &Grid<char>, word: &str) -> Vec<Match> {
+        let word: Vec<char> = word.chars().collect();
+        let mut matches = Vec::new();
+        for start in grid.points() {
+            for direction in Point::ALL_DIRECTIONS {
+                let mut ray = grid.ray(start, direction).map(|(_, &c)| c);
+                if !word.is_empty() && word.iter().all(|&c| ray.next() == Some(c)) {
+                    matches.push(Match { start, direction });
+                }
+            }
+        }
+        matches
+    }
+
+    #[test]
+    fn test_example_counts() {
+        let grid = Grid::parse(TEST_INPUT).unwrap();
+        // Every XMAS is a SAMX read the other way
+        let found = search_words(&grid, &["XMAS", "SAMX"]);
+        let counts: Vec<_> = found.iter().map(|w| w.count()).collect();
+        assert_eq!(counts, [18, 18]);
+    }
+
+    #[test]
+    fn test_matches_agree_with_brute_force() {
+        let grid = Grid::parse(TEST_INPUT).unwrap();
+        let words = ["XMAS", "AMA", "A", "MM", "SAMXMAS", "", "Q"];
+        for found in search_words(&grid, &words) {
+            let mut matches = found.matches.clone();
+            matches.sort();
+            let mut expected = brute_force(&grid, &found.word);
+            expected.sort();
+            assert_eq!(matches, expected, "{}", found.word);
+        }
+    }
+
+    #[test]
+    fn test_match_coordinates() {
+        let grid = Grid::parse("XMAS\n.A..\nS..X").unwrap();
+        let found = search_words(&grid, &["SAMX", "XA"]);
+        assert_eq!(
+            found[0].matches,
+            [Match {
+                start: Point::new(3, 0),
+                direction: Point::LEFT,
+            }]
+        );
+        assert_eq!(
+            found[1].matches,
+            [Match {
+                start: Point::new(0, 0),
+                direction: Point::DOWN_RIGHT,
+            }]
+        );
+    }
+}
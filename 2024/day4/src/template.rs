@@ -0,0 +1,244 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_core::ParseError;
+use grid::{Grid, Point};
+
+/// Matches any cell in a [`Template`].
+pub const WILDCARD: char = '.';
+
+/// A small rectangular shape to look for in a grid, such as the puzzle's
+/// X of two `MAS`:
+///
+/// ```text
+/// M.S
+/// .A.
+/// M.S
+/// ```
+///
+/// Every cell must be equal to the grid's cell under it, except
+/// [`WILDCARD`]s, which match anything but must still lie inside the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    width: usize,
+    height: usize,
+    // The cells that are not wildcards, by offset from the top left, in
+    // row order so that equal shapes compare equal
+    cells: Vec<(Point, char)>,
+}
+
+/// Where a template was found: the grid cell under its top left corner, and
+/// which of [`Template::orientations`] matched there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Placement {
+    pub top_left: Point,
+    pub orientation: usize,
+}
+
+impl Template {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The template turned through each quarter turn, then each of those
+    /// mirrored left to right, keeping only the first of any that look the
+    /// same. The template itself comes first.
+    pub fn orientations(&self) -> Vec<Template> {
+        let mut turned = vec![self.clone()];
+        for _ in 0..3 {
+            let next = turned[turned.len() - 1].turn_right();
+            turned.push(next);
+        }
+        let mirrored: Vec<_> = turned.iter().map(Template::mirror).collect();
+
+        let mut distinct: Vec<Template> = Vec::new();
+        for template in turned.into_iter().chain(mirrored) {
+            if !distinct.contains(&template) {
+                distinct.push(template);
+            }
+        }
+        distinct
+    }
+
+    /// Whether the template, as it stands, matches `grid` with its top left
+    /// corner on `top_left`.
+    pub fn matches_at(&self, grid: &Grid<char>, top_left: Point) -> bool {
+        let far = Point::from((self.width, self.height)) - Point::new(1, 1);
+        grid.contains(top_left)
+            && grid.contains(top_left + far)
+            && self
+                .cells
+                .iter()
+                .all(|&(offset, c)| grid.get(top_left + offset) == Some(&c))
+    }
+
+    /// Every placement of every orientation of the template in `grid`, row
+    /// by row. Different orientations matching at the same place count
+    /// separately, but a symmetrical template is not counted twice for
+    /// looking the same turned over.
+    pub fn find_all(&self, grid: &Grid<char>) -> Vec<Placement> {
+        let orientations = self.orientations();
+        grid.points()
+            .flat_map(|top_left| {
+                orientations
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, template)| template.matches_at(grid, top_left))
+                    .map(move |(orientation, _)| Placement {
+                        top_left,
+                        orientation,
+                    })
+            })
+            .collect()
+    }
+
+    /// How many times the template appears in `grid`, in any orientation.
+    pub fn count(&self, grid: &Grid<char>) -> usize {
+        self.find_all(grid).len()
+    }
+
+    // A quarter turn clockwise: the left column becomes the top row
+    fn turn_right(&self) -> Template {
+        let last_row = self.height as isize - 1;
+        self.transform(self.height, self.width, |p| Point::new(last_row - p.y, p.x))
+    }
+
+    fn mirror(&self) -> Template {
+        let last_column = self.width as isize - 1;
+        self.transform(self.width, self.height, |p| {
+            Point::new(last_column - p.x, p.y)
+        })
+    }
+
+    fn transform(&self, width: usize, height: usize, f: impl Fn(Point) -> Point) -> Template {
+        let mut cells: Vec<_> = self.cells.iter().map(|&(p, c)| (f(p), c)).collect();
+        cells.sort_by_key(|&(p, _)| (p.y, p.x));
+        Template {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+/// Parses one row per line, or rows separated by `/` (`M.S / .A. / M.S`),
+/// ignoring spaces around each row.
+impl FromStr for Template {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Template, ParseError> {
+        let rows: Vec<&str> = s
+            .split(['\n', '/'])
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .collect();
+        let shape = Grid::parse(&rows.join("\n"))?;
+        if shape.width() == 0 {
+            return Err(ParseError::new("a template needs at least one cell"));
+        }
+        Ok(Template {
+            width: shape.width(),
+            height: shape.height(),
+            cells: shape
+                .iter()
+                .filter(|&(_, &c)| c != WILDCARD)
+                .map(|(p, &c)| (p, c))
+                .collect(),
+        })
+    }
+}
+
+/// Renders one row per line, with wildcards as [`WILDCARD`].
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut shape = Grid::new(self.width, self.height, WILDCARD);
+        for &(p, c) in &self.cells {
+            shape[p] = c;
+        }
+        write!(f, "{}", shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    fn rendered(template: &str) -> Vec<String> {
+        let template: Template = template.parse().unwrap();
+        template
+            .orientations()
+            .iter()
+            .map(|t| t.to_string().replace('\n', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_forms() {
+        let inline: Template = "M.S / .A. / M.S".parse().unwrap();
+        let lines: Template = "M.S\n.A.\nM.S\n".parse().unwrap();
+        assert_eq!(inline, lines);
+        assert_eq!((inline.width(), inline.height()), (3, 3));
+        assert_eq!(inline.to_string(), "M.S\n.A.\nM.S");
+
+        let err = "AB/C".parse::<Template>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: row has 1 cells, expected 2"
+        );
+        assert!(" / ".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn test_orientations_drop_duplicates() {
+        assert_eq!(
+            rendered("M.S/.A./M.S"),
+            ["M.S/.A./M.S", "M.M/.A./S.S", "S.M/.A./S.M", "S.S/.A./M.M"]
+        );
+        assert_eq!(rendered("AB."), ["AB.", "A/B/.", ".BA", "./B/A"]);
+        assert_eq!(rendered("AB/CD").len(), 8);
+        assert_eq!(rendered("A.A/.A./A.A").len(), 1);
+    }
+
+    #[test]
+    fn test_x_mas_example() {
+        let grid = Grid::parse(TEST_INPUT).unwrap();
+        let template: Template = "M.S/.A./M.S".parse().unwrap();
+        assert_eq!(template.count(&grid), 9);
+        let first = template.find_all(&grid)[0];
+        assert_eq!(
+            first,
+            Placement {
+                top_left: Point::new(1, 0),
+                orientation: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_wildcards_stay_inside_the_grid() {
+        let grid = Grid::parse("AB\nCD").unwrap();
+        // Across the top row, or down the left column
+        let template: Template = "A.".parse().unwrap();
+        assert_eq!(template.count(&grid), 2);
+        let template: Template = "A./..".parse().unwrap();
+        assert_eq!(template.count(&grid), 1);
+        let template: Template = "A../...".parse().unwrap();
+        assert_eq!(template.count(&grid), 0);
+    }
+}
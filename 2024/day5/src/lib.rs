@@ -0,0 +1,181 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_core::{numbered_lines, parse_field, Answer, ParseError, Solution};
+
+pub struct Day5;
+
+// `(x, y)`: page x must be printed before page y
+type Rule = (u32, u32);
+
+fn parse(input: &str) -> Result<(Vec<Rule>, Vec<Vec<u32>>), ParseError> {
+    // Leading blank lines would otherwise end the rules section immediately
+    let mut lines = numbered_lines(input).skip_while(|(_, line)| line.trim().is_empty());
+
+    // Parse ordering rules
+    let mut ordering_rules = Vec::new();
+    for (line_no, line) in &mut lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            break; // Blank line indicates end of ordering rules
+        }
+        let Some((x, y)) = trimmed.split_once('|') else {
+            return Err(ParseError::in_line(
+                line_no,
+                line,
+                trimmed,
+                "expected an ordering rule like `47|53`",
+            ));
+        };
+        let x = parse_field(line_no, line, x, "a page number")?;
+        let y = parse_field(line_no, line, y, "a page number")?;
+        ordering_rules.push((x, y));
+    }
+
+    // Parse updates
+    let mut updates = Vec::new();
+    for (line_no, line) in &mut lines {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            let pages = trimmed
+                .split(',')
+                .map(|s| parse_field(line_no, line, s.trim(), "a page number"))
+                .collect::<Result<Vec<u32>, _>>()?;
+            updates.push(pages);
+        }
+    }
+
+    if updates.is_empty() {
+        return Err(ParseError::new(
+            "expected ordering rules, a blank line, then updates",
+        ));
+    }
+    Ok((ordering_rules, updates))
+}
+
+// An update is correctly ordered if no applicable rule is violated
+fn is_ordered(pages: &[u32], ordering_rules: &[Rule]) -> bool {
+    // Map page numbers to their positions
+    let mut position = HashMap::new();
+    for (idx, &page) in pages.iter().enumerate() {
+        position.insert(page, idx);
+    }
+
+    // Check applicable ordering rules
+    for &(x, y) in ordering_rules {
+        if let (Some(&pos_x), Some(&pos_y)) = (position.get(&x), position.get(&y)) {
+            if pos_x >= pos_y {
+                // Rule violated
+                return false;
+            }
+        }
+        // If one of the pages is not in the update, ignore the rule
+    }
+    true
+}
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 5;
+
+    type Input = (Vec<Rule>, Vec<Vec<u32>>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1((ordering_rules, updates): &Self::Input) -> Answer {
+        let mut total_part1 = 0;
+
+        for pages in updates {
+            if is_ordered(pages, ordering_rules) {
+                // Update is correctly ordered
+                let middle_idx = pages.len() / 2; // For odd lengths, this is the middle index
+                total_part1 += pages[middle_idx];
+            }
+        }
+
+        total_part1.into()
+    }
+
+    fn part2((ordering_rules, updates): &Self::Input) -> Answer {
+        let mut total_part2 = 0;
+
+        // Reorder incorrectly-ordered updates
+        for pages in updates {
+            if is_ordered(pages, ordering_rules) {
+                continue;
+            }
+            // Reorder the pages using topological sort
+            if let Some(sorted_pages) = topological_sort(pages, ordering_rules) {
+                // Find the middle page number
+                let middle_idx = sorted_pages.len() / 2;
+                total_part2 += sorted_pages[middle_idx];
+            } else {
+                // If no valid ordering exists (shouldn't happen), handle accordingly
+                log::warn!("No valid ordering exists for update: {:?}", pages);
+            }
+        }
+
+        total_part2.into()
+    }
+}
+
+// Function to perform topological sort
+fn topological_sort(
+    pages: &[u32],
+    ordering_rules: &[Rule],
+) -> Option<Vec<u32>> {
+    // Build graph and in-degree map
+    let mut graph: HashMap<u32, Vec<u32>> = HashMap::new();
+    let mut in_degree: HashMap<u32, usize> = HashMap::new();
+    let page_set: HashSet<u32> = pages.iter().cloned().collect();
+
+    // Initialize in-degree of all pages to 0
+    for &page in &page_set {
+        in_degree.insert(page, 0);
+        graph.insert(page, Vec::new());
+    }
+
+    // Build the graph and in-degree map based on applicable ordering rules
+    for &(x, y) in ordering_rules {
+        if page_set.contains(&x) && page_set.contains(&y) {
+            // x must come before y
+            graph.get_mut(&x).unwrap().push(y);
+            *in_degree.get_mut(&y).unwrap() += 1;
+        }
+    }
+
+    // Use a min-heap or sorted list for deterministic output
+    let mut zero_in_degree: Vec<u32> = in_degree
+        .iter()
+        .filter(|&(_, &deg)| deg == 0)
+        .map(|(&page, _)| page)
+        .collect();
+    zero_in_degree.sort(); // Ensure deterministic ordering
+
+    let mut sorted_pages = Vec::new();
+
+    while !zero_in_degree.is_empty() {
+        // Remove a page from zero_in_degree
+        let page = zero_in_degree.remove(0);
+        sorted_pages.push(page);
+
+        // Decrease in-degree of neighbors
+        for &neighbor in &graph[&page] {
+            let deg = in_degree.get_mut(&neighbor).unwrap();
+            *deg -= 1;
+            if *deg == 0 {
+                zero_in_degree.push(neighbor);
+            }
+        }
+
+        zero_in_degree.sort(); // Keep the list sorted for deterministic output
+    }
+
+    if sorted_pages.len() == page_set.len() {
+        Some(sorted_pages)
+    } else {
+        // Cycle detected (shouldn't happen in this problem)
+        None
+    }
+}
\ No newline at end of file
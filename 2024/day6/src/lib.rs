@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Grid, Point};
+
+pub struct Day6;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    fn turn_right(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    fn step(&self) -> Point {
+        match self {
+            Direction::Up => Point::UP,
+            Direction::Right => Point::RIGHT,
+            Direction::Down => Point::DOWN,
+            Direction::Left => Point::LEFT,
+        }
+    }
+
+    fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+struct State {
+    pos: Point,
+    dir: Direction,
+}
+
+fn is_loop(map: &Grid<char>, start_pos: Point, start_dir: Direction) -> bool {
+    let mut visited: HashSet<State> = HashSet::new();
+    let mut current_pos = start_pos;
+    let mut current_dir = start_dir;
+    let mut steps = 0;
+    const MAX_STEPS: usize = 10000; // Increased limit
+    
+    while steps < MAX_STEPS {
+        steps += 1;
+        
+        let state = State {
+            pos: current_pos,
+            dir: current_dir,
+        };
+        
+        // If we've seen this exact state before, it's a loop
+        if !visited.insert(state) {
+            return true;
+        }
+        
+        // Try to move forward, leaving the map ends the walk
+        let next_pos = current_pos + current_dir.step();
+        match map.get(next_pos) {
+            None => return false,
+            // Check for obstacle and move/turn accordingly
+            Some('#') => current_dir = current_dir.turn_right(),
+            Some(_) => current_pos = next_pos,
+        }
+    }
+    
+    false
+}
+
+fn count_loop_positions(map: &Grid<char>, guard_pos: Point, start_dir: Direction) -> usize {
+    let mut count = 0;
+    
+    for pos in map.points() {
+        // Skip if not empty space or guard position
+        if map[pos] != '.' || pos == guard_pos {
+            continue;
+        }
+        
+        // Create test map with new obstacle
+        let mut test_map = map.clone();
+        test_map[pos] = '#';
+        
+        if is_loop(&test_map, guard_pos, start_dir) {
+            count += 1;
+            log::debug!("Found loop at ({}, {})", pos.x, pos.y);
+        }
+    }
+    
+    count
+}
+
+fn solve_part1(map: &Grid<char>, start_pos: Point, start_dir: Direction) -> usize {
+    let mut visited: HashSet<Point> = HashSet::new();
+    let mut current_pos = start_pos;
+    let mut current_dir = start_dir;
+
+    loop {
+        visited.insert(current_pos);
+        
+        let next_pos = current_pos + current_dir.step();
+        match map.get(next_pos) {
+            None => break,
+            Some('#') => current_dir = current_dir.turn_right(),
+            Some(_) => current_pos = next_pos,
+        }
+    }
+    
+    visited.len()
+}
+
+fn parse(input: &str) -> Result<(Grid<char>, Point, Direction), ParseError> {
+    let map = Grid::parse(input)?;
+
+    let mut guard = None;
+    for (pos, &c) in map.iter() {
+        let at = |message: String| ParseError::at(pos.y as usize + 1, pos.x as usize + 1, message);
+        match (c, Direction::from_char(c)) {
+            ('.' | '#', _) => {}
+            (_, Some(_)) if guard.is_some() => return Err(at("a second guard".to_string())),
+            (_, Some(direction)) => guard = Some((pos, direction)),
+            (_, None) => return Err(at(format!("unexpected `{}` on the map", c))),
+        }
+    }
+
+    let (guard_pos, direction) =
+        guard.ok_or_else(|| ParseError::new("no guard (^, >, v or <) on the map"))?;
+    Ok((map, guard_pos, direction))
+}
+
+impl Solution for Day6 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 6;
+
+    type Input = (Grid<char>, Point, Direction);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&(ref map, guard_pos, direction): &Self::Input) -> Answer {
+        solve_part1(map, guard_pos, direction).into()
+    }
+
+    fn part2(&(ref map, guard_pos, direction): &Self::Input) -> Answer {
+        count_loop_positions(map, guard_pos, direction).into()
+    }
+}
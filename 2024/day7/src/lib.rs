@@ -0,0 +1,706 @@
+use aoc_core::{numbered_lines, parse_field, Answer, ParseError, Solution};
+
+mod number;
+mod operators;
+mod order;
+
+use order::Binding;
+
+pub use number::Number;
+pub use order::{Order, Precedence};
+pub use operators::{
+    parse_operator, Add, Concatenate, Divide, Exponent, Multiply, Operator, Subtract, Undo, Xor,
+};
+
+pub struct Day7;
+
+#[derive(Debug)]
+pub struct Equation<N = i64> {
+    pub test_value: N,
+    pub numbers: Vec<N>,
+}
+
+impl<N: Number> Equation<N> {
+    /// Writes the equation out with `operators` between its numbers, e.g.
+    /// `81 + 40 * 27 = 3267`.
+    pub fn render(&self, operators: &[&dyn Operator<N>]) -> String {
+        assert_eq!(
+            operators.len() + 1,
+            self.numbers.len(),
+            "need one operator between each pair of numbers"
+        );
+        let mut expression = self.numbers[0].to_string();
+        for (op, n) in operators.iter().zip(&self.numbers[1..]) {
+            expression += &format!(" {} {}", op, n);
+        }
+        format!("{} = {}", expression, self.test_value)
+    }
+}
+
+/// The operator assignments that make an equation true.
+#[derive(Debug)]
+pub struct Solutions<'a, N: Number = i64> {
+    /// The assignments found, left to right, up to the requested limit.
+    pub operators: Vec<Vec<&'a dyn Operator<N>>>,
+    /// How many assignments work in total, including any not collected.
+    pub count: u64,
+}
+
+// The solver prunes on the assumption that no value ever shrinks, which only
+// holds for non-negative numbers
+fn parse_non_negative<N: Number>(
+    line_no: usize,
+    line: &str,
+    token: &str,
+    what: &str,
+) -> Result<N, ParseError> {
+    let n: N = parse_field(line_no, line, token, what)?;
+    if n.is_negative() {
+        return Err(ParseError::in_line(
+            line_no,
+            line,
+            token,
+            format!("expected {}, found negative `{}`", what, token),
+        ));
+    }
+    Ok(n)
+}
+
+// Parse a line of input into an Equation struct
+fn parse_equation<N: Number>(line_no: usize, line: &str) -> Result<Equation<N>, ParseError> {
+    let Some((test_value, numbers)) = line.split_once(':') else {
+        return Err(ParseError::at(
+            line_no,
+            1,
+            "expected an equation like `190: 10 19`",
+        ));
+    };
+    let test_value = parse_non_negative(line_no, line, test_value.trim(), "a test value")?;
+    let numbers = numbers
+        .split_whitespace()
+        .map(|n| parse_non_negative(line_no, line, n, "a number"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if numbers.is_empty() {
+        return Err(ParseError::in_line(
+            line_no,
+            line,
+            &line[line.len()..],
+            "expected at least one number after `:`",
+        ));
+    }
+    Ok(Equation { test_value, numbers })
+}
+
+// Visits the operator assignments that make an equation true.
+//
+// When every operator can be undone, the search works backwards from the
+// target: the last operator must turn some value of the remaining numbers
+// into `target`, so undo it and recurse. Operators that cannot have produced
+// `target` (a non-divisor, missing trailing digits, an operand larger than
+// the target) are pruned straight away. Otherwise it tries every operator
+// from the left, dropping a value once it is past the target and nothing
+// after it can bring it back down.
+//
+// Right to left is the mirror image: it folds from the last number, each
+// number before it going in as a left operand, and drops a value past the
+// target once nothing still to come can shrink it from the left.
+//
+// Any other order is searched from the left too, keeping the operators that
+// must wait for tighter-binding ones on a stack, as in shunting-yard parsing.
+// When no operator can shrink either operand, every value on the stack ends
+// up inside the result, so one past the target ends the branch.
+struct Search<'a, N: Number> {
+    operators: &'a [&'a dyn Operator<N>],
+    numbers: &'a [N],
+    right_to_left: bool,
+    // How each operator binds, unless folding straight through
+    bindings: Option<Vec<Binding>>,
+    // Values below zero can never reach the target again
+    non_negative: bool,
+    // Whether some operator can shrink a value at or after each position
+    can_shrink_from: Vec<bool>,
+    // Whether some operator can shrink a value from the left with a number
+    // before each position
+    can_shrink_before: Vec<bool>,
+    // The grouped search's stack of values and of operators waiting on
+    // them, and the reductions made since, to take back on the way out
+    values: Vec<N>,
+    pending: Vec<usize>,
+    reduced: Vec<(N, N, usize)>,
+    limit: usize,
+    count_all: bool,
+    // Index into `operators` of the operator after each number
+    slots: Vec<usize>,
+    found: Solutions<'a, N>,
+}
+
+impl<'a, N: Number> Search<'a, N> {
+    fn new(
+        equation: &'a Equation<N>,
+        operators: &'a [&'a dyn Operator<N>],
+        order: &Order,
+        limit: usize,
+    ) -> Self {
+        let numbers = &equation.numbers;
+        let mut can_shrink_from = vec![false; numbers.len() + 1];
+        for (i, n) in numbers.iter().enumerate().rev() {
+            can_shrink_from[i] = can_shrink_from[i + 1] || operators.iter().any(|op| op.can_shrink(n));
+        }
+        let mut can_shrink_before = vec![false; numbers.len() + 1];
+        for (i, n) in numbers.iter().enumerate() {
+            can_shrink_before[i + 1] =
+                can_shrink_before[i] || operators.iter().any(|op| op.can_shrink_right(n));
+        }
+        Search {
+            operators,
+            numbers,
+            right_to_left: *order == Order::RightToLeft,
+            bindings: order.bindings(operators),
+            non_negative: operators.iter().all(|op| op.keeps_non_negative()),
+            can_shrink_from,
+            can_shrink_before,
+            values: Vec::new(),
+            pending: Vec::new(),
+            reduced: Vec::new(),
+            limit,
+            count_all: true,
+            slots: vec![0; numbers.len().saturating_sub(1)],
+            found: Solutions {
+                operators: Vec::new(),
+                count: 0,
+            },
+        }
+    }
+
+    fn run(&mut self, target: &N) {
+        if self.numbers.is_empty() {
+            return;
+        }
+        // Any input will do: `undo` is `None` for all of them or for none
+        let (zero, one) = (N::from_i64(0), N::from_i64(1));
+        let undoable = self.operators.iter().all(|op| op.undo(&zero, &one).is_some());
+        let last = self.numbers.len() - 1;
+        if self.bindings.is_some() {
+            self.values.push(self.numbers[0].clone());
+            if !self.overshoots(target) {
+                self.grouped(1, target);
+            }
+            self.values.clear();
+        } else if self.right_to_left {
+            self.leftward(self.numbers[last].clone(), last, target);
+        } else if undoable {
+            self.backward(target, self.numbers.len());
+        } else {
+            self.forward(self.numbers[0].clone(), 1, self.numbers.len(), Some(target));
+        }
+    }
+
+    // Searches for assignments making `numbers[..len]` equal `target`.
+    // Returns true once the search can stop.
+    fn backward(&mut self, target: &N, len: usize) -> bool {
+        let last = &self.numbers[len - 1];
+        if len == 1 {
+            return target == last && self.record();
+        }
+        for (i, op) in self.operators.iter().enumerate() {
+            self.slots[len - 2] = i;
+            let undo = op.undo(target, last).expect("operator can be undone");
+            let stop = match undo {
+                Undo::Impossible => false,
+                Undo::Left(left) if self.non_negative && left.is_negative() => false,
+                Undo::Left(left) => self.backward(&left, len - 1),
+                // Whatever the numbers before evaluate to will do
+                Undo::AnyLeft => self.forward(self.numbers[0].clone(), 1, len - 1, None),
+            };
+            if stop {
+                return true;
+            }
+        }
+        false
+    }
+
+    // Searches for assignments making `numbers[..end]` equal `target` (or
+    // anything at all), given that `numbers[..next]` evaluates to `value`.
+    // Returns true once the search can stop.
+    fn forward(&mut self, value: N, next: usize, end: usize, target: Option<&N>) -> bool {
+        if next == end {
+            return target.is_none_or(|target| *target == value) && self.record();
+        }
+        if target.is_some_and(|target| value > *target) && !self.can_shrink_from[next] {
+            return false;
+        }
+        for (i, op) in self.operators.iter().enumerate() {
+            if let Some(value) = op.apply(&value, &self.numbers[next]) {
+                if self.non_negative && value.is_negative() {
+                    continue;
+                }
+                self.slots[next - 1] = i;
+                if self.forward(value, next + 1, end, target) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // Searches for assignments making the equation equal `target` folded
+    // from the right, given that `numbers[len..]` evaluates to `value`.
+    // Returns true once the search can stop.
+    fn leftward(&mut self, value: N, len: usize, target: &N) -> bool {
+        if len == 0 {
+            return *target == value && self.record();
+        }
+        if value > *target && !self.can_shrink_before[len] {
+            return false;
+        }
+        for (i, op) in self.operators.iter().enumerate() {
+            if let Some(value) = op.apply(&self.numbers[len - 1], &value) {
+                if self.non_negative && value.is_negative() {
+                    continue;
+                }
+                self.slots[len - 1] = i;
+                if self.leftward(value, len - 1, target) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // Searches for assignments making the equation equal `target` when its
+    // numbers up to `next` have left `self.values` and the operators between
+    // them in `self.pending` still to apply. Leaves both as it found them.
+    // Returns true once the search can stop.
+    fn grouped(&mut self, next: usize, target: &N) -> bool {
+        let mark = self.reduced.len();
+        if next == self.numbers.len() {
+            let stop = self.reduce(None) && self.values[0] == *target && self.record();
+            self.restore(mark);
+            return stop;
+        }
+        for i in 0..self.operators.len() {
+            let mut stop = false;
+            if self.reduce(Some(i)) && !self.overshoots(target) {
+                self.values.push(self.numbers[next].clone());
+                self.pending.push(i);
+                self.slots[next - 1] = i;
+                stop = !self.overshoots(target) && self.grouped(next + 1, target);
+                self.values.pop();
+                self.pending.pop();
+            }
+            self.restore(mark);
+            if stop {
+                return true;
+            }
+        }
+        false
+    }
+
+    // Whether the value on top of the stack is past the target for good
+    fn overshoots(&self, target: &N) -> bool {
+        self.non_negative
+            && !self.can_shrink_from[0]
+            && !self.can_shrink_before[self.numbers.len()]
+            && self.values.last().is_some_and(|value| value > target)
+    }
+
+    // Applies the pending operators that bind before operator `next` (all of
+    // them at the end), noting each in `reduced`. Returns false if one of
+    // them is undefined.
+    fn reduce(&mut self, next: Option<usize>) -> bool {
+        let bindings = self.bindings.as_ref().expect("a grouped search has bindings");
+        while let Some(&top) = self.pending.last() {
+            if next.is_some_and(|next| !bindings[top].before(bindings[next])) {
+                break;
+            }
+            let right = self.values.pop().expect("one value per operator and one more");
+            let left = self.values.pop().expect("one value per operator and one more");
+            match self.operators[top].apply(&left, &right) {
+                Some(value) if !(self.non_negative && value.is_negative()) => {
+                    self.pending.pop();
+                    self.values.push(value);
+                    self.reduced.push((left, right, top));
+                }
+                _ => {
+                    self.values.push(left);
+                    self.values.push(right);
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    // Takes back the reductions made since `reduced` was `mark` long
+    fn restore(&mut self, mark: usize) {
+        while self.reduced.len() > mark {
+            let (left, right, op) = self.reduced.pop().expect("checked the length");
+            self.values.pop();
+            self.values.push(left);
+            self.values.push(right);
+            self.pending.push(op);
+        }
+    }
+
+    fn record(&mut self) -> bool {
+        self.found.count += 1;
+        if self.found.operators.len() < self.limit {
+            let operators = self.slots.iter().map(|&i| self.operators[i]).collect();
+            self.found.operators.push(operators);
+        }
+        !self.count_all && self.found.operators.len() >= self.limit
+    }
+}
+
+fn can_make_value<N: Number>(
+    equation: &Equation<N>,
+    operators: &[&dyn Operator<N>],
+    order: &Order,
+) -> bool {
+    let mut search = Search::new(equation, operators, order, 0);
+    search.count_all = false;
+    search.run(&equation.test_value);
+    search.found.count > 0
+}
+
+/// Every assignment of `operators` that makes `equation` true, evaluating
+/// in `order`.
+pub fn solutions<'a, N: Number>(
+    equation: &'a Equation<N>,
+    operators: &'a [&'a dyn Operator<N>],
+    order: &Order,
+) -> Solutions<'a, N> {
+    first_solutions(equation, operators, order, usize::MAX)
+}
+
+/// Like `solutions`, but keeps only the first `limit` assignments found. The
+/// count still covers all of them.
+pub fn first_solutions<'a, N: Number>(
+    equation: &'a Equation<N>,
+    operators: &'a [&'a dyn Operator<N>],
+    order: &Order,
+    limit: usize,
+) -> Solutions<'a, N> {
+    let mut search = Search::new(equation, operators, order, limit);
+    search.run(&equation.test_value);
+    search.found
+}
+
+/// Parses the equations, with numbers of any width: `i64` for the puzzle,
+/// `i128` (or `BigInt` with the `big` feature) for larger inputs.
+pub fn parse<N: Number>(input: &str) -> Result<Vec<Equation<N>>, ParseError> {
+    numbered_lines(input)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_no, line)| parse_equation(line_no, line))
+        .collect()
+}
+
+/// The equations that some assignment of `operators` makes true, evaluating
+/// in `order`.
+pub fn satisfiable<'a, N: Number>(
+    equations: &'a [Equation<N>],
+    operators: &'a [&'a dyn Operator<N>],
+    order: &'a Order,
+) -> impl Iterator<Item = &'a Equation<N>> {
+    equations.iter().filter(move |eq| {
+        if log::log_enabled!(log::Level::Trace) {
+            match first_solutions(eq, operators, order, 1).operators.first() {
+                Some(ops) => log::trace!("Solved: {}", eq.render(ops)),
+                None => log::trace!("No solution: {:?}", eq),
+            }
+        }
+        can_make_value(eq, operators, order)
+    })
+}
+
+/// Sums the test values of the equations that some assignment of
+/// `operators` makes true, evaluating in `order`, or `None` if the total
+/// does not fit in `N`.
+pub fn solve<N: Number>(
+    equations: &[Equation<N>],
+    operators: &[&dyn Operator<N>],
+    order: &Order,
+) -> Option<N> {
+    satisfiable(equations, operators, order)
+        .try_fold(N::from_i64(0), |total, eq| total.checked_add(&eq.test_value))
+}
+
+// The puzzle's answer, summed in an i128 so it cannot overflow
+fn total(equations: &[Equation], operators: &[&dyn Operator]) -> Answer {
+    satisfiable(equations, operators, &Order::LeftToRight)
+        .map(|eq| i128::from(eq.test_value))
+        .sum::<i128>()
+        .into()
+}
+
+impl Solution for Day7 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 7;
+
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    // Part 1: Using Add and Multiply operators
+    fn part1(equations: &Self::Input) -> Answer {
+        total(equations, &[&Add, &Multiply])
+    }
+
+    // Part 2: Including the Concatenate operator
+    fn part2(equations: &Self::Input) -> Answer {
+        total(equations, &[&Add, &Multiply, &Concatenate::DECIMAL])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    const PART1: &[&dyn Operator] = &[&Add, &Multiply];
+    const PART2: &[&dyn Operator] = &[&Add, &Multiply, &Concatenate::DECIMAL];
+    const LEFT: &Order = &Order::LeftToRight;
+
+    // Each equation's solutions, rendered
+    fn rendered(input: &str, operators: &[&dyn Operator], order: &Order) -> Vec<Vec<String>> {
+        parse(input)
+            .unwrap()
+            .iter()
+            .map(|eq| {
+                let found = solutions(eq, operators, order);
+                found.operators.iter().map(|ops| eq.render(ops)).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(solve(&parse(TEST_INPUT).unwrap(), PART1, LEFT), Some(3749));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(solve(&parse(TEST_INPUT).unwrap(), PART2, LEFT), Some(11387));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        let err = |input| parse::<i64>(input).unwrap_err().to_string();
+        assert_eq!(
+            err("190: 10 19\n3267: 81 4O 27"),
+            "line 2, column 10: expected a number, found `4O`"
+        );
+        assert_eq!(
+            err("190: 10 19\n3267 81 40 27"),
+            "line 2, column 1: expected an equation like `190: 10 19`"
+        );
+        assert_eq!(
+            err("3267:"),
+            "line 1, column 6: expected at least one number after `:`"
+        );
+    }
+
+    #[test]
+    fn test_concatenate_operator() {
+        let operators: &[&dyn Operator] = &[&Concatenate::DECIMAL];
+        let equations = parse("156: 15 6\n156: 1 6").unwrap();
+        assert!(can_make_value(&equations[0], operators, LEFT));
+        assert!(!can_make_value(&equations[1], operators, LEFT));
+
+        // `0 || i64::MAX` overflows, whichever way it is searched
+        let equations = parse("9223372036854775807: 0 9223372036854775807").unwrap();
+        let one_level = &"||".parse().unwrap();
+        assert!(!can_make_value(&equations[0], operators, LEFT));
+        assert_eq!(solutions(&equations[0], operators, one_level).count, 0);
+    }
+
+    #[test]
+    fn test_equations_are_solved_independently() {
+        // Same length and target: a verdict for one must not leak to the other
+        let equations = parse("10: 5 5\n10: 3 3\n").unwrap();
+        assert_eq!(solve(&equations, PART1, LEFT), Some(10));
+    }
+
+    #[test]
+    fn test_long_equation() {
+        // Brute force would try 3^29 operator assignments
+        let numbers = vec!["1"; 30].join(" ");
+        let equations = parse(&format!("30: {}", numbers)).unwrap();
+        assert_eq!(solve(&equations, PART2, LEFT), Some(30));
+    }
+
+    #[test]
+    fn test_multiply_by_zero() {
+        let equations = parse("0: 7 3 0\n5: 7 3 0").unwrap();
+        assert!(can_make_value(&equations[0], PART1, LEFT));
+        assert!(!can_make_value(&equations[1], PART1, LEFT));
+    }
+
+    #[test]
+    fn test_solutions_of_example() {
+        let equations = parse(TEST_INPUT).unwrap();
+
+        let found = solutions(&equations[1], PART1, LEFT);
+        assert_eq!(found.count, 2);
+        assert_eq!(
+            rendered("3267: 81 40 27", PART1, LEFT),
+            [["81 * 40 + 27 = 3267", "81 + 40 * 27 = 3267"]]
+        );
+
+        assert_eq!(solutions(&equations[4], PART1, LEFT).count, 0);
+        assert_eq!(
+            rendered("7290: 6 8 6 15", PART2, LEFT),
+            [["6 * 8 || 6 * 15 = 7290"]]
+        );
+    }
+
+    #[test]
+    fn test_first_solutions_still_counts_all() {
+        // 2 + 2 and 2 * 2 are both 4
+        let equations = parse("4: 2 2").unwrap();
+        let found = first_solutions(&equations[0], PART1, LEFT, 1);
+        assert_eq!((found.count, found.operators.len()), (2, 1));
+
+        // Multiplying by a trailing zero makes the rest of the operators free
+        let equations = parse("0: 7 3 5 0").unwrap();
+        let found = first_solutions(&equations[0], PART1, LEFT, 2);
+        assert_eq!((found.count, found.operators.len()), (4, 2));
+        assert_eq!(solutions(&equations[0], PART1, LEFT).operators.len(), 4);
+    }
+
+    #[test]
+    fn test_other_operator_sets() {
+        // Subtraction lets values go negative on the way
+        let operators: &[&dyn Operator] = &[&Add, &Subtract];
+        assert_eq!(rendered("6: 3 5 8", operators, LEFT), [["3 - 5 + 8 = 6"]]);
+
+        // Division has no inverse, so this is searched forwards
+        let operators: &[&dyn Operator] = &[&Divide, &Exponent];
+        assert_eq!(rendered("16: 8 2 4", operators, LEFT), [["8 ^ 2 / 4 = 16"]]);
+        assert_eq!(rendered("3: 7 2", operators, LEFT), [["7 / 2 = 3"]]);
+
+        let operators: &[&dyn Operator] = &[&Xor, &Concatenate::new(2)];
+        assert_eq!(rendered("15: 3 2 1", operators, LEFT), [["3 ||2 2 xor 1 = 15"]]);
+    }
+
+    #[test]
+    fn test_forward_search_stops_past_the_target() {
+        // Nothing here can shrink 10, so none of the 2^24 assignments is tried
+        let numbers = vec!["1"; 24].join(" ");
+        let equations = parse(&format!("5: 10 {}", numbers)).unwrap();
+        let operators: &[&dyn Operator] = &[&Add, &Exponent];
+        assert_eq!(solutions(&equations[0], operators, LEFT).count, 0);
+
+        // ...but a later zero exponent can
+        assert_eq!(
+            rendered("1: 10 1 0", operators, LEFT),
+            [["10 + 1 ^ 0 = 1", "10 ^ 1 ^ 0 = 1"]]
+        );
+    }
+
+    #[test]
+    fn test_wider_numbers() {
+        // 2^32 * 2^32 = 2^64
+        let input = "18446744073709551616: 4294967296 4294967296";
+        assert!(parse::<i64>(input).is_err());
+        let equations = parse::<i128>(input).unwrap();
+        let operators: &[&dyn Operator<i128>] = &[&Add, &Multiply];
+        assert_eq!(solve(&equations, operators, LEFT), Some(1 << 64));
+
+        // Products that overflow are not solutions, whatever they wrap to
+        let equations = parse("0: 4294967296 4294967296").unwrap();
+        assert_eq!(solve(&equations, PART1, LEFT), Some(0));
+        assert_eq!(solutions(&equations[0], PART1, LEFT).count, 0);
+    }
+
+    #[test]
+    fn test_total_overflow() {
+        let input = format!("{0}: {0}\n{0}: {0}", i64::MAX);
+        let equations = parse(&input).unwrap();
+        assert_eq!(solve(&equations, PART1, LEFT), None);
+        assert_eq!(Day7::part1(&equations), Answer::Number(2 * i64::MAX as i128));
+    }
+
+    #[test]
+    fn test_precedence_orders() {
+        let standard = &Order::Precedence(Precedence::standard());
+        assert_eq!(rendered("11: 1 2 5", PART1, standard), [["1 + 2 * 5 = 11"]]);
+        assert_eq!(
+            rendered("3267: 81 40 27", PART1, standard),
+            [["81 * 40 + 27 = 3267"]]
+        );
+        // Powers group right to left
+        let operators: &[&dyn Operator] = &[&Exponent];
+        assert_eq!(rendered("512: 2 3 2", operators, standard), [["2 ^ 3 ^ 2 = 512"]]);
+        assert!(rendered("512: 2 3 2", operators, LEFT)[0].is_empty());
+
+        let right = &Order::RightToLeft;
+        assert_eq!(rendered("8: 2 3 1", PART1, right), [["2 * 3 + 1 = 8"]]);
+
+        let operators: &[&dyn Operator] = &[&Add, &Concatenate::DECIMAL];
+        let custom = &"|| > +".parse().unwrap();
+        assert_eq!(rendered("57: 1 5 6", operators, custom), [["1 + 5 || 6 = 57"]]);
+        assert!(rendered("57: 1 5 6", operators, LEFT)[0].is_empty());
+
+        // Listed under any spelling, a concatenation keeps its place
+        let operators: &[&dyn Operator] = &[&Add, &Concatenate::new(2)];
+        let custom = &"||2 > +".parse().unwrap();
+        assert_eq!(rendered("5: 2 1 1", operators, custom), [["2 + 1 ||2 1 = 5"]]);
+        assert!(rendered("5: 2 1 1", operators, LEFT)[0].is_empty());
+        let operators: &[&dyn Operator] = &[&Add, &Concatenate::DECIMAL];
+        let custom = &"||10 > +".parse().unwrap();
+        assert_eq!(rendered("57: 1 5 6", operators, custom), [["1 + 5 || 6 = 57"]]);
+    }
+
+    #[test]
+    fn test_one_level_is_left_to_right() {
+        // The grouped search must agree with the backward one
+        let equations = parse(TEST_INPUT).unwrap();
+        let one_level = &"+ * ||".parse().unwrap();
+        assert_eq!(solve(&equations, PART2, one_level), Some(11387));
+        for eq in &equations {
+            assert_eq!(
+                solutions(eq, PART2, one_level).count,
+                solutions(eq, PART2, LEFT).count
+            );
+        }
+    }
+
+    #[test]
+    fn test_right_to_left_fold_agrees_with_grouping() {
+        // One right-associative level groups the same way, through the stack
+        let equations = parse(TEST_INPUT).unwrap();
+        let right = &Order::RightToLeft;
+        let grouped = &Order::Precedence(
+            Precedence::new(&[&["+", "*", "||"]]).right_associative(&["+", "*", "||"]),
+        );
+        for eq in &equations {
+            assert_eq!(
+                solutions(eq, PART2, right).count,
+                solutions(eq, PART2, grouped).count
+            );
+        }
+        assert_eq!(solve(&equations, PART2, right), solve(&equations, PART2, grouped));
+    }
+
+    #[test]
+    fn test_parse_rejects_negative_numbers() {
+        assert_eq!(
+            parse::<i64>("10: 5 -5").unwrap_err().to_string(),
+            "line 1, column 7: expected a number, found negative `-5`"
+        );
+    }
+}
\ No newline at end of file
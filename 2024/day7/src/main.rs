@@ -1,141 +1,164 @@
-use std::collections::HashMap;
 use std::fs;
-use std::io;
-
-#[derive(Debug)]
-struct Equation {
-    test_value: i64,
-    numbers: Vec<i64>,
-}
-
-#[derive(Clone, Copy, Debug)]
-enum Operator {
-    Add,
-    Multiply,
-    Concatenate,
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use day7::{first_solutions, parse, parse_operator, solve, Number, Operator, Order};
+
+const USAGE: &str = "\
+Usage:
+    day7 [--operators <list>] [--order <order>] [--solutions <n>]
+         [--numbers <type>] [<path> | -]
+
+Sums the test values of the equations that some assignment of the operators
+makes true, reading the equations from <path>, stdin (`-`) or this day's
+input.txt.
+
+    --operators <list>   comma-separated symbols (default `+,*,||`):
+                         +  -  *  /  ^  xor  ||  ||<base> (2 to 36)
+    --order <order>      how operators group: left (default, as in the
+                         puzzle), right, standard (^, then * /, then + -),
+                         or levels tightest first such as `|| > * > +`
+    --solutions <n>      also print up to <n> assignments for each
+                         equation and how many there are in total
+    --numbers <type>     i64 (default), i128, or big for arbitrary
+                         precision (needs the `big` feature); values that
+                         overflow the type are never solutions";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Numbers {
+    I64,
+    I128,
+    Big,
 }
 
-// Parse a line of input into an Equation struct
-fn parse_equation(line: &str) -> Option<Equation> {
-    let mut parts = line.split(':');
-    let test_value = parts.next()?.trim().parse().ok()?;
-    let numbers = parts
-        .next()?
-        .trim()
-        .split_whitespace()
-        .filter_map(|n| n.parse().ok())
-        .collect();
-
-    Some(Equation { test_value, numbers })
+#[derive(Debug)]
+struct Options {
+    operators: Vec<String>,
+    order: Order,
+    solutions: Option<usize>,
+    numbers: Numbers,
+    input: Option<PathBuf>,
 }
 
-// Evaluate the expression with the given operators
-fn evaluate(numbers: &[i64], operators: &[Operator]) -> i64 {
-    let mut result = numbers[0];
-    for (i, &op) in operators.iter().enumerate() {
-        match op {
-            Operator::Add => result += numbers[i + 1],
-            Operator::Multiply => result *= numbers[i + 1],
-            Operator::Concatenate => {
-                // Concatenate the numbers as strings
-                let concat = format!("{}{}", result, numbers[i + 1]);
-                result = concat.parse().unwrap_or(0);
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        operators: Vec::new(),
+        order: Order::LeftToRight,
+        solutions: None,
+        numbers: Numbers::I64,
+        input: Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("input.txt")),
+    };
+    let mut operators = "+,*,||".to_string();
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--operators" => operators = args.next().ok_or("--operators needs a value")?,
+            "--order" => {
+                options.order = args.next().ok_or("--order needs a value")?.parse()?;
+            }
+            "--solutions" => {
+                let n = args.next().ok_or("--solutions needs a value")?;
+                let n = n.parse().map_err(|_| format!("invalid count `{}`", n))?;
+                options.solutions = Some(n);
+            }
+            "--numbers" => {
+                options.numbers = match args.next().as_deref() {
+                    Some("i64") => Numbers::I64,
+                    Some("i128") => Numbers::I128,
+                    Some("big") if cfg!(feature = "big") => Numbers::Big,
+                    Some("big") => return Err("built without the `big` feature".to_string()),
+                    Some(other) => return Err(format!("unknown number type `{}`", other)),
+                    None => return Err("--numbers needs a value".to_string()),
+                }
             }
+            _ if arg.starts_with("--") => return Err(format!("unknown flag `{}`", arg)),
+            _ if input.is_none() => input = Some(arg),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
         }
     }
-    result
-}
 
-// Generate all possible operator combinations using memoization
-fn can_make_value(
-    equation: &Equation,
-    operators_list: &[Operator],
-    memo: &mut HashMap<(usize, i64), bool>,
-) -> bool {
-    let key = (equation.numbers.len(), equation.test_value);
-    if let Some(&result) = memo.get(&key) {
-        return result;
+    options.operators = operators.split(',').map(|s| s.trim().to_string()).collect();
+    for symbol in &options.operators {
+        parse_operator::<i64>(symbol)?;
     }
+    match input.as_deref() {
+        Some("-") => options.input = None,
+        Some(path) => options.input = Some(PathBuf::from(path)),
+        None => {}
+    }
+    Ok(options)
+}
 
-    let operators_needed = equation.numbers.len() - 1;
-    let total_combinations = operators_list.len().pow(operators_needed as u32);
-
-    for i in 0..total_combinations {
-        let mut operators = Vec::with_capacity(operators_needed);
-        let mut n = i;
-        // Generate operator combinations
-        for _ in 0..operators_needed {
-            let op_index = n % operators_list.len();
-            operators.push(operators_list[op_index]);
-            n /= operators_list.len();
+fn run(options: &Options) -> Result<(), String> {
+    let (name, input) = match &options.input {
+        Some(path) => {
+            let input = fs::read_to_string(path)
+                .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+            (path.display().to_string(), input)
         }
-        // Check if the evaluation matches the test value
-        if evaluate(&equation.numbers, &operators) == equation.test_value {
-            memo.insert(key, true);
-            return true;
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("cannot read stdin: {}", e))?;
+            ("stdin".to_string(), input)
         }
+    };
+    match options.numbers {
+        Numbers::I64 => solve_as::<i64>(&name, &input, options),
+        Numbers::I128 => solve_as::<i128>(&name, &input, options),
+        #[cfg(feature = "big")]
+        Numbers::Big => solve_as::<num_bigint::BigInt>(&name, &input, options),
+        #[cfg(not(feature = "big"))]
+        Numbers::Big => unreachable!("rejected while parsing the arguments"),
     }
-    memo.insert(key, false);
-    false
 }
 
-fn solve(input: &str, operators_list: &[Operator]) -> i64 {
-    let mut memo = HashMap::new();
-    input
-        .lines()
-        .filter_map(parse_equation)
-        .filter(|eq| can_make_value(eq, operators_list, &mut memo))
-        .map(|eq| eq.test_value)
-        .sum()
-}
-
-fn main() -> io::Result<()> {
-    let input = fs::read_to_string("input.txt")?;
-
-    // Part 1: Using Add and Multiply operators
-    let part1_operators = &[Operator::Add, Operator::Multiply];
-    let part1_result = solve(&input, part1_operators);
-    println!("Part 1: {}", part1_result);
-
-    // Part 2: Including the Concatenate operator
-    let part2_operators = &[Operator::Add, Operator::Multiply, Operator::Concatenate];
-    let part2_result = solve(&input, part2_operators);
-    println!("Part 2: {}", part2_result);
-
+fn solve_as<N: Number>(name: &str, input: &str, options: &Options) -> Result<(), String> {
+    let equations = parse::<N>(input).map_err(|e| format!("{}: {}", name, e))?;
+    let operators = options
+        .operators
+        .iter()
+        .map(|symbol| parse_operator(symbol))
+        .collect::<Result<Vec<_>, _>>()?;
+    let operators: Vec<&dyn Operator<N>> = operators.iter().map(|op| op.as_ref()).collect();
+
+    if let Some(limit) = options.solutions {
+        for eq in &equations {
+            let found = first_solutions(eq, &operators, &options.order, limit);
+            let numbers: Vec<_> = eq.numbers.iter().map(|n| n.to_string()).collect();
+            println!(
+                "{}: {} ({} solutions)",
+                eq.test_value,
+                numbers.join(" "),
+                found.count
+            );
+            for ops in &found.operators {
+                println!("    {}", eq.render(ops));
+            }
+        }
+    }
+    let total = solve(&equations, &operators, &options.order)
+        .ok_or("the total overflows; try --numbers i128")?;
+    println!("{}", total);
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "\
-190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
-
-    #[test]
-    fn test_part1_example() {
-        let operators = &[Operator::Add, Operator::Multiply];
-        assert_eq!(solve(TEST_INPUT, operators), 3749);
-    }
-
-    #[test]
-    fn test_part2_example() {
-        let operators = &[Operator::Add, Operator::Multiply, Operator::Concatenate];
-        assert_eq!(solve(TEST_INPUT, operators), 11387);
-    }
-
-    #[test]
-    fn test_concatenate_operator() {
-        let numbers = vec![15, 6];
-        let operators = vec![Operator::Concatenate];
-        assert_eq!(evaluate(&numbers, &operators), 156);
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The integers equations are solved over. Every operation is checked, so
+/// overflow prunes a branch of the search instead of wrapping or panicking;
+/// pick a wider type when the inputs need it.
+pub trait Number: Clone + Ord + fmt::Debug + fmt::Display + FromStr + Send + Sync {
+    fn from_i64(n: i64) -> Self;
+    fn is_zero(&self) -> bool;
+    fn is_negative(&self) -> bool;
+    fn to_u32(&self) -> Option<u32>;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    /// Division rounding towards zero; `None` when dividing by zero.
+    fn checked_div(&self, other: &Self) -> Option<Self>;
+    fn checked_rem(&self, other: &Self) -> Option<Self>;
+    fn checked_pow(&self, exponent: u32) -> Option<Self>;
+    fn xor(&self, other: &Self) -> Self;
+}
+
+macro_rules! primitive {
+    ($($t:ty),*) => {
+        $(impl Number for $t {
+            fn from_i64(n: i64) -> Self {
+                n.into()
+            }
+
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+
+            fn is_negative(&self) -> bool {
+                *self < 0
+            }
+
+            fn to_u32(&self) -> Option<u32> {
+                u32::try_from(*self).ok()
+            }
+
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_add(*self, *other)
+            }
+
+            fn checked_sub(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_sub(*self, *other)
+            }
+
+            fn checked_mul(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_mul(*self, *other)
+            }
+
+            fn checked_div(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_div(*self, *other)
+            }
+
+            fn checked_rem(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_rem(*self, *other)
+            }
+
+            fn checked_pow(&self, exponent: u32) -> Option<Self> {
+                <$t>::checked_pow(*self, exponent)
+            }
+
+            fn xor(&self, other: &Self) -> Self {
+                *self ^ *other
+            }
+        })*
+    };
+}
+
+primitive!(i64, i128);
+
+#[cfg(feature = "big")]
+mod big {
+    use num_bigint::{BigInt, Sign};
+    use num_traits::{ToPrimitive, Zero};
+
+    use super::Number;
+
+    // Powers are the one operation that can exhaust memory rather than
+    // overflow, so results beyond this many bits count as overflow
+    const MAX_POW_BITS: u64 = 1 << 16;
+
+    impl Number for BigInt {
+        fn from_i64(n: i64) -> Self {
+            n.into()
+        }
+
+        fn is_zero(&self) -> bool {
+            Zero::is_zero(self)
+        }
+
+        fn is_negative(&self) -> bool {
+            self.sign() == Sign::Minus
+        }
+
+        fn to_u32(&self) -> Option<u32> {
+            ToPrimitive::to_u32(self)
+        }
+
+        fn checked_add(&self, other: &Self) -> Option<Self> {
+            Some(self + other)
+        }
+
+        fn checked_sub(&self, other: &Self) -> Option<Self> {
+            Some(self - other)
+        }
+
+        fn checked_mul(&self, other: &Self) -> Option<Self> {
+            Some(self * other)
+        }
+
+        fn checked_div(&self, other: &Self) -> Option<Self> {
+            (!Zero::is_zero(other)).then(|| self / other)
+        }
+
+        fn checked_rem(&self, other: &Self) -> Option<Self> {
+            (!Zero::is_zero(other)).then(|| self % other)
+        }
+
+        fn checked_pow(&self, exponent: u32) -> Option<Self> {
+            let bits = self.bits().saturating_mul(exponent.into());
+            (bits <= MAX_POW_BITS).then(|| self.pow(exponent))
+        }
+
+        fn xor(&self, other: &Self) -> Self {
+            self ^ other
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitives_report_overflow() {
+        assert_eq!(Number::checked_mul(&i64::MAX, &2), None);
+        assert_eq!(
+            Number::checked_mul(&(i64::MAX as i128), &2),
+            Some(2 * i64::MAX as i128)
+        );
+        assert_eq!(Number::checked_rem(&i64::MIN, &-1), None);
+        assert_eq!(Number::to_u32(&-1i64), None);
+    }
+
+    #[cfg(feature = "big")]
+    #[test]
+    fn test_big_integers() {
+        use num_bigint::BigInt;
+
+        let big = |s: &str| s.parse::<BigInt>().unwrap();
+        let max = BigInt::from(i128::MAX);
+        assert_eq!(
+            Number::checked_mul(&max, &BigInt::from(2)),
+            Some(big("340282366920938463463374607431768211454"))
+        );
+        assert_eq!(Number::checked_div(&max, &BigInt::from(0)), None);
+        assert_eq!(
+            Number::checked_pow(&BigInt::from(2), 100),
+            Some(big("1267650600228229401496703205376"))
+        );
+        assert_eq!(Number::checked_pow(&BigInt::from(2), 1 << 20), None);
+    }
+}
@@ -0,0 +1,453 @@
+use std::fmt;
+
+use crate::number::Number;
+
+/// A binary operator the solver can place between two numbers of an
+/// equation. Equations are evaluated left to right, so `left` is always the
+/// value of everything before the operator. Its `Display` is the symbol used
+/// when rendering an equation.
+pub trait Operator<N: Number = i64>: fmt::Display + fmt::Debug + Sync {
+    /// `left <op> right`, or `None` if that is undefined or overflows.
+    fn apply(&self, left: &N, right: &N) -> Option<N>;
+
+    /// The `left` values for which `apply(left, right) == Some(target)`.
+    /// Operators that cannot be undone return `None` for every input, and
+    /// equations using them are searched forwards instead of backwards from
+    /// the target.
+    fn undo(&self, _target: &N, _right: &N) -> Option<Undo<N>> {
+        None
+    }
+
+    /// Whether non-negative operands always give a non-negative result. When
+    /// every operator in a set does, the search discards negative values.
+    fn keeps_non_negative(&self) -> bool {
+        false
+    }
+
+    /// Whether `apply(left, right)` can be less than some non-negative
+    /// `left`. The forward search stops extending a value that is already
+    /// past the target once nothing left to apply can shrink it.
+    fn can_shrink(&self, _right: &N) -> bool {
+        true
+    }
+
+    /// Whether `apply(left, right)` can be less than some non-negative
+    /// `right`: `can_shrink` from the other side, for searches that fold
+    /// from the last number.
+    fn can_shrink_right(&self, _left: &N) -> bool {
+        true
+    }
+}
+
+/// What undoing an operator leaves for the numbers before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Undo<N = i64> {
+    /// No value can produce the target.
+    Impossible,
+    /// Exactly this value produces the target.
+    Left(N),
+    /// Every value produces the target, e.g. anything times zero is zero.
+    AnyLeft,
+}
+
+impl<N> Undo<N> {
+    fn from_option(left: Option<N>) -> Undo<N> {
+        left.map_or(Undo::Impossible, Undo::Left)
+    }
+}
+
+#[derive(Debug)]
+pub struct Add;
+
+#[derive(Debug)]
+pub struct Subtract;
+
+#[derive(Debug)]
+pub struct Multiply;
+
+/// Integer division, rounding towards zero.
+#[derive(Debug)]
+pub struct Divide;
+
+/// `left` raised to the power `right`.
+#[derive(Debug)]
+pub struct Exponent;
+
+/// Bitwise exclusive or.
+#[derive(Debug)]
+pub struct Xor;
+
+/// Writes the digits of `right` after those of `left`, in `base`.
+#[derive(Debug)]
+pub struct Concatenate {
+    base: i64,
+}
+
+impl Concatenate {
+    pub const DECIMAL: Concatenate = Concatenate { base: 10 };
+
+    pub fn new(base: i64) -> Concatenate {
+        assert!(base >= 2, "cannot write numbers in base {}", base);
+        Concatenate { base }
+    }
+
+    // The smallest power of the base above `n`, i.e. base^(digits of n), or
+    // `None` if that does not fit in an `N`. Counts digits by multiplying
+    // rather than formatting, so concatenating never allocates.
+    fn scale<N: Number>(&self, n: &N) -> Option<N> {
+        let base = N::from_i64(self.base);
+        let mut scale = base.clone();
+        while scale <= *n {
+            scale = scale.checked_mul(&base)?;
+        }
+        Some(scale)
+    }
+}
+
+impl<N: Number> Operator<N> for Add {
+    fn apply(&self, left: &N, right: &N) -> Option<N> {
+        left.checked_add(right)
+    }
+
+    fn undo(&self, target: &N, right: &N) -> Option<Undo<N>> {
+        Some(Undo::from_option(target.checked_sub(right)))
+    }
+
+    fn keeps_non_negative(&self) -> bool {
+        true
+    }
+
+    fn can_shrink(&self, right: &N) -> bool {
+        right.is_negative()
+    }
+
+    fn can_shrink_right(&self, left: &N) -> bool {
+        left.is_negative()
+    }
+}
+
+impl<N: Number> Operator<N> for Subtract {
+    fn apply(&self, left: &N, right: &N) -> Option<N> {
+        left.checked_sub(right)
+    }
+
+    fn undo(&self, target: &N, right: &N) -> Option<Undo<N>> {
+        Some(Undo::from_option(target.checked_add(right)))
+    }
+
+    fn can_shrink(&self, right: &N) -> bool {
+        !right.is_zero() && !right.is_negative()
+    }
+}
+
+impl<N: Number> Operator<N> for Multiply {
+    fn apply(&self, left: &N, right: &N) -> Option<N> {
+        left.checked_mul(right)
+    }
+
+    fn undo(&self, target: &N, right: &N) -> Option<Undo<N>> {
+        Some(if right.is_zero() && target.is_zero() {
+            Undo::AnyLeft
+        } else if right.is_zero() {
+            Undo::Impossible
+        } else if target.checked_rem(right).is_some_and(|rem| rem.is_zero()) {
+            Undo::from_option(target.checked_div(right))
+        } else {
+            Undo::Impossible
+        })
+    }
+
+    fn keeps_non_negative(&self) -> bool {
+        true
+    }
+
+    fn can_shrink(&self, right: &N) -> bool {
+        right.is_zero() || right.is_negative()
+    }
+
+    fn can_shrink_right(&self, left: &N) -> bool {
+        left.is_zero() || left.is_negative()
+    }
+}
+
+impl<N: Number> Operator<N> for Divide {
+    fn apply(&self, left: &N, right: &N) -> Option<N> {
+        left.checked_div(right)
+    }
+
+    fn keeps_non_negative(&self) -> bool {
+        true
+    }
+}
+
+impl<N: Number> Operator<N> for Exponent {
+    fn apply(&self, left: &N, right: &N) -> Option<N> {
+        left.checked_pow(right.to_u32()?)
+    }
+
+    fn keeps_non_negative(&self) -> bool {
+        true
+    }
+
+    fn can_shrink(&self, right: &N) -> bool {
+        // x^0 is 1
+        right.is_zero()
+    }
+
+    fn can_shrink_right(&self, left: &N) -> bool {
+        // 2^x > x, but 0^x and 1^x are at most 1
+        *left < N::from_i64(2)
+    }
+}
+
+impl<N: Number> Operator<N> for Xor {
+    fn apply(&self, left: &N, right: &N) -> Option<N> {
+        Some(left.xor(right))
+    }
+
+    fn undo(&self, target: &N, right: &N) -> Option<Undo<N>> {
+        Some(Undo::Left(target.xor(right)))
+    }
+
+    fn keeps_non_negative(&self) -> bool {
+        true
+    }
+}
+
+impl<N: Number> Operator<N> for Concatenate {
+    fn apply(&self, left: &N, right: &N) -> Option<N> {
+        if left.is_negative() || right.is_negative() {
+            return None;
+        }
+        left.checked_mul(&self.scale(right)?)?.checked_add(right)
+    }
+
+    fn undo(&self, target: &N, right: &N) -> Option<Undo<N>> {
+        if target.is_negative() || right.is_negative() {
+            return Some(Undo::Impossible);
+        }
+        Some(match self.scale(right) {
+            Some(scale) if target.checked_rem(&scale).as_ref() == Some(right) => {
+                Undo::from_option(target.checked_div(&scale))
+            }
+            // Past the scale an `N` can hold, `apply` fails even for
+            // `0 || right`, so nothing undoes to it either
+            _ => Undo::Impossible,
+        })
+    }
+
+    fn keeps_non_negative(&self) -> bool {
+        true
+    }
+
+    fn can_shrink(&self, _right: &N) -> bool {
+        false
+    }
+
+    fn can_shrink_right(&self, _left: &N) -> bool {
+        false
+    }
+}
+
+macro_rules! symbol {
+    ($($operator:ty => $symbol:literal),* $(,)?) => {
+        $(impl fmt::Display for $operator {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str($symbol)
+            }
+        })*
+    };
+}
+
+symbol! {
+    Add => "+",
+    Subtract => "-",
+    Multiply => "*",
+    Divide => "/",
+    Exponent => "^",
+    Xor => "xor",
+}
+
+impl fmt::Display for Concatenate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.base {
+            10 => f.write_str("||"),
+            base => write!(f, "||{}", base),
+        }
+    }
+}
+
+/// Parses an operator from its symbol: `+`, `-`, `*`, `/`, `^`, `xor`, `||`
+/// (decimal concatenation) or `||<base>` with a base from 2 to 36.
+pub fn parse_operator<N: Number>(symbol: &str) -> Result<Box<dyn Operator<N>>, String> {
+    Ok(match symbol {
+        "+" => Box::new(Add),
+        "-" => Box::new(Subtract),
+        "*" => Box::new(Multiply),
+        "/" => Box::new(Divide),
+        "^" => Box::new(Exponent),
+        "xor" => Box::new(Xor),
+        "||" => Box::new(Concatenate::DECIMAL),
+        _ => {
+            let base = symbol
+                .strip_prefix("||")
+                .and_then(|base| base.parse().ok())
+                .filter(|base| (2..=36).contains(base))
+                .ok_or_else(|| format!("unknown operator `{}`", symbol))?;
+            Box::new(Concatenate::new(base))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: &[i64] = &[0, 1, 2, 3, 7, 10, 15, 99, 100, 12345];
+
+    fn apply(op: &dyn Operator, left: i64, right: i64) -> Option<i64> {
+        op.apply(&left, &right)
+    }
+
+    // Undoing an operator must give back exactly the left operands that
+    // produce the target
+    #[test]
+    fn test_undo_inverts_apply() {
+        let operators: &[&dyn Operator] = &[
+            &Add,
+            &Subtract,
+            &Multiply,
+            &Xor,
+            &Concatenate::DECIMAL,
+            &Concatenate::new(2),
+        ];
+        for &op in operators {
+            for &left in SAMPLES {
+                for &right in SAMPLES {
+                    let Some(target) = apply(op, left, right) else {
+                        continue;
+                    };
+                    match op.undo(&target, &right).unwrap() {
+                        Undo::Left(undone) => assert_eq!(
+                            apply(op, undone, right),
+                            Some(target),
+                            "{} {} {}",
+                            left,
+                            op,
+                            right
+                        ),
+                        Undo::AnyLeft => assert_eq!(right, 0),
+                        Undo::Impossible => panic!("cannot undo {} {} {}", left, op, right),
+                    }
+                }
+            }
+        }
+    }
+
+    // `can_shrink` must be true whenever an operator gives less than `left`
+    #[test]
+    fn test_can_shrink() {
+        let operators: &[&dyn Operator] = &[
+            &Add,
+            &Subtract,
+            &Multiply,
+            &Divide,
+            &Exponent,
+            &Xor,
+            &Concatenate::DECIMAL,
+        ];
+        for &op in operators {
+            for &left in SAMPLES {
+                for &right in SAMPLES {
+                    if apply(op, left, right).is_some_and(|value| value < left) {
+                        assert!(op.can_shrink(&right), "{} {} {}", left, op, right);
+                    }
+                }
+            }
+        }
+        assert!(!Operator::<i64>::can_shrink(&Multiply, &2));
+        assert!(!Operator::<i64>::can_shrink(&Exponent, &2));
+    }
+
+    // And `can_shrink_right` whenever one gives less than `right`
+    #[test]
+    fn test_can_shrink_right() {
+        let operators: &[&dyn Operator] = &[
+            &Add,
+            &Subtract,
+            &Multiply,
+            &Divide,
+            &Exponent,
+            &Xor,
+            &Concatenate::DECIMAL,
+        ];
+        for &op in operators {
+            for &left in SAMPLES {
+                for &right in SAMPLES {
+                    if apply(op, left, right).is_some_and(|value| value < right) {
+                        assert!(op.can_shrink_right(&left), "{} {} {}", left, op, right);
+                    }
+                }
+            }
+        }
+        assert!(!Operator::<i64>::can_shrink_right(&Multiply, &2));
+        assert!(!Operator::<i64>::can_shrink_right(&Exponent, &2));
+        assert!(!Operator::<i64>::can_shrink_right(&Concatenate::DECIMAL, &0));
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(apply(&Subtract, 3, 5), Some(-2));
+        assert_eq!(apply(&Divide, 7, 2), Some(3));
+        assert_eq!(apply(&Divide, 7, 0), None);
+        assert_eq!(apply(&Exponent, 2, 10), Some(1024));
+        assert_eq!(apply(&Exponent, 2, -1), None);
+        assert_eq!(apply(&Exponent, 10, 19), None);
+        assert_eq!(apply(&Xor, 0b1100, 0b1010), Some(0b0110));
+        assert_eq!(apply(&Concatenate::DECIMAL, 12, 345), Some(12345));
+        assert_eq!(apply(&Concatenate::DECIMAL, 12, 0), Some(120));
+        assert_eq!(apply(&Concatenate::new(2), 0b11, 0b101), Some(0b11101));
+        assert_eq!(apply(&Concatenate::new(16), 0xa, 0xbc), Some(0xabc));
+        assert_eq!(apply(&Concatenate::DECIMAL, 1, i64::MAX), None);
+        assert_eq!(apply(&Concatenate::DECIMAL, -1, 2), None);
+    }
+
+    #[test]
+    fn test_wider_numbers() {
+        let op: &dyn Operator<i128> = &Concatenate::DECIMAL;
+        assert_eq!(
+            op.apply(&1, &(i64::MAX as i128)),
+            Some(19223372036854775807)
+        );
+        assert_eq!(apply(&Concatenate::DECIMAL, 1, i64::MAX), None);
+    }
+
+    #[test]
+    fn test_undo_concatenate() {
+        let undo = |target: i64, right: i64| {
+            Operator::<i64>::undo(&Concatenate::DECIMAL, &target, &right).unwrap()
+        };
+        assert_eq!(undo(12345, 345), Undo::Left(12));
+        assert_eq!(undo(12345, 45), Undo::Left(123));
+        assert_eq!(undo(100, 0), Undo::Left(10));
+        assert_eq!(undo(12345, 12345), Undo::Left(0));
+        assert_eq!(undo(12345, 35), Undo::Impossible);
+        // `0 || i64::MAX` overflows going forwards, so it can't come back
+        assert_eq!(undo(i64::MAX, i64::MAX), Undo::Impossible);
+        assert_eq!(
+            Operator::<i64>::apply(&Concatenate::DECIMAL, &0, &i64::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_operator() {
+        let symbol = |s| parse_operator::<i64>(s).map(|op| op.to_string());
+        for s in ["+", "-", "*", "/", "^", "xor", "||", "||2", "||36"] {
+            assert_eq!(symbol(s).as_deref(), Ok(s));
+        }
+        assert_eq!(symbol("||10").as_deref(), Ok("||"));
+        assert_eq!(symbol("||1"), Err("unknown operator `||1`".to_string()));
+        assert_eq!(symbol("%"), Err("unknown operator `%`".to_string()));
+    }
+}
@@ -0,0 +1,219 @@
+use std::str::FromStr;
+
+use crate::number::Number;
+use crate::operators::{parse_operator, Operator};
+
+/// How the operators of an equation group.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Order {
+    /// Strictly left to right, ignoring the usual rules, as in the puzzle.
+    #[default]
+    LeftToRight,
+    /// Strictly right to left: `a - b - c` is `a - (b - c)`.
+    RightToLeft,
+    /// Tighter-binding operators first, then left to right within a level.
+    Precedence(Precedence),
+}
+
+/// Precedence levels for operators, by symbol. Operators without a level
+/// bind more loosely than every listed one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Precedence {
+    // Tightest first
+    levels: Vec<Vec<String>>,
+    // Symbols that group right to left within their level
+    right: Vec<String>,
+}
+
+/// Where an operator sits in an `Order`: higher levels bind tighter, and
+/// right-associative operators group `a ^ b ^ c` as `a ^ (b ^ c)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Binding {
+    pub level: usize,
+    pub right: bool,
+}
+
+impl Binding {
+    /// Whether a pending `self` is applied before `next` goes on.
+    pub fn before(self, next: Binding) -> bool {
+        self.level > next.level || (self.level == next.level && !next.right)
+    }
+}
+
+impl Precedence {
+    /// School rules: `^` (grouping right to left), then `*` and `/`, then
+    /// `+` and `-`, then everything else.
+    pub fn standard() -> Precedence {
+        Precedence::new(&[&["^"], &["*", "/"], &["+", "-"]]).right_associative(&["^"])
+    }
+
+    /// Levels of operator symbols, tightest first. Symbols are stored as the
+    /// operators display them (`||10` as `||`); one `parse_operator` doesn't
+    /// know, such as a custom operator's, is kept as written.
+    pub fn new(levels: &[&[&str]]) -> Precedence {
+        let levels = levels
+            .iter()
+            .map(|level| level.iter().map(|s| normalise(s)).collect())
+            .collect();
+        Precedence {
+            levels,
+            right: Vec::new(),
+        }
+    }
+
+    /// Makes `symbols` group right to left, like `^` in `standard`.
+    pub fn right_associative(mut self, symbols: &[&str]) -> Precedence {
+        self.right.extend(symbols.iter().map(|s| normalise(s)));
+        self
+    }
+
+    fn level_of(&self, symbol: &str) -> usize {
+        self.levels
+            .iter()
+            .position(|level| level.iter().any(|s| s == symbol))
+            .map_or(0, |i| self.levels.len() - i)
+    }
+}
+
+// How the operator `symbol` names displays itself
+fn normalise(symbol: &str) -> String {
+    parse_operator::<i64>(symbol).map_or_else(|_| symbol.to_string(), |op| op.to_string())
+}
+
+impl Order {
+    /// How each of `operators` binds, or `None` for a straight fold either
+    /// way, which the solver handles without a stack.
+    pub(crate) fn bindings<N: Number>(
+        &self,
+        operators: &[&dyn Operator<N>],
+    ) -> Option<Vec<Binding>> {
+        if matches!(self, Order::LeftToRight | Order::RightToLeft) {
+            return None;
+        }
+        operators
+            .iter()
+            .map(|op| self.binding(&op.to_string()))
+            .collect()
+    }
+
+    fn binding(&self, symbol: &str) -> Option<Binding> {
+        match self {
+            Order::LeftToRight => None,
+            Order::RightToLeft => Some(Binding {
+                level: 0,
+                right: true,
+            }),
+            Order::Precedence(precedence) => Some(Binding {
+                level: precedence.level_of(symbol),
+                right: precedence.right.iter().any(|s| s == symbol),
+            }),
+        }
+    }
+}
+
+/// `left`, `right`, `standard`, or a table of operator symbols from
+/// tightest to loosest such as `^ > * / > + -`.
+impl FromStr for Order {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Order, String> {
+        match spec.trim() {
+            "left" => return Ok(Order::LeftToRight),
+            "right" => return Ok(Order::RightToLeft),
+            "standard" => return Ok(Order::Precedence(Precedence::standard())),
+            _ => {}
+        }
+        let mut levels = Vec::new();
+        for level in spec.split('>') {
+            let symbols = level
+                .split_whitespace()
+                // Unlike `Precedence::new`, a table names only known
+                // operators, so one that doesn't parse is an error
+                .map(|symbol| Ok(parse_operator::<i64>(symbol)?.to_string()))
+                .collect::<Result<Vec<_>, String>>()?;
+            if symbols.is_empty() {
+                return Err(format!("empty precedence level in `{}`", spec));
+            }
+            levels.push(symbols);
+        }
+        Ok(Order::Precedence(Precedence {
+            levels,
+            right: Vec::new(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_order() {
+        assert_eq!("left".parse(), Ok(Order::LeftToRight));
+        assert_eq!("right".parse(), Ok(Order::RightToLeft));
+        assert_eq!(
+            "standard".parse(),
+            Ok(Order::Precedence(Precedence::standard()))
+        );
+        assert_eq!(
+            "|| > * + ".parse(),
+            Ok(Order::Precedence(Precedence::new(&[&["||"], &["*", "+"]])))
+        );
+        assert_eq!(
+            "||10 > ||2  +".parse(),
+            Ok(Order::Precedence(Precedence::new(&[
+                &["||"],
+                &["||2", "+"]
+            ])))
+        );
+        assert_eq!(
+            "* >> +".parse::<Order>(),
+            Err("empty precedence level in `* >> +`".to_string())
+        );
+        assert_eq!(
+            "* > %".parse::<Order>(),
+            Err("unknown operator `%`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_new_normalises_symbols() {
+        assert_eq!(
+            Precedence::new(&[&["||10"], &["+"]]),
+            Precedence::new(&[&["||"], &["+"]])
+        );
+        let order = Order::Precedence(Precedence::new(&[&["||10"], &["+"]]));
+        let binding = |symbol| order.binding(symbol).unwrap();
+        assert!(binding("||").before(binding("+")));
+        // Custom symbols are kept as they are
+        assert_eq!(Precedence::new(&[&["%"]]).level_of("%"), 1);
+    }
+
+    #[test]
+    fn test_right_associative_normalises_symbols() {
+        let precedence = Precedence::new(&[&["||"]]).right_associative(&["||10"]);
+        assert_eq!(
+            precedence,
+            Precedence::new(&[&["||"]]).right_associative(&["||"])
+        );
+        let order = Order::Precedence(precedence);
+        assert!(!order
+            .binding("||")
+            .unwrap()
+            .before(order.binding("||").unwrap()));
+    }
+
+    #[test]
+    fn test_bindings() {
+        let standard = Order::Precedence(Precedence::standard());
+        let binding = |symbol| standard.binding(symbol).unwrap();
+        assert!(binding("*").before(binding("+")));
+        assert!(!binding("+").before(binding("*")));
+        // Equal levels group left to right, except for powers
+        assert!(binding("+").before(binding("-")));
+        assert!(!binding("^").before(binding("^")));
+        // Unlisted operators bind loosest
+        assert!(binding("+").before(binding("||")));
+        assert_eq!(Order::LeftToRight.binding("+"), None);
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Grid, Point};
+
+pub struct Day8;
+
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub struct AntennaMap {
+    map: Grid<char>,
+    // Antenna positions grouped by frequency
+    antennas: HashMap<char, Vec<Point>>,
+}
+
+fn parse(input: &str) -> Result<AntennaMap, ParseError> {
+    // Read the input map
+    let map = Grid::parse(input)?;
+
+    // Collect antenna positions grouped by frequency
+    let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
+    for (pos, &ch) in map.iter() {
+        if ch != '.' {
+            antennas.entry(ch).or_default().push(pos);
+        }
+    }
+
+    Ok(AntennaMap { map, antennas })
+}
+
+impl Solution for Day8 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 8;
+
+    type Input = AntennaMap;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(AntennaMap { map, antennas }: &Self::Input) -> Answer {
+        let mut antinodes_part1 = HashSet::new();
+
+        // For each frequency
+        for positions in antennas.values() {
+            // For each pair of antennas
+            for (i, &p1) in positions.iter().enumerate() {
+                for &p2 in &positions[i + 1..] {
+                    // Internal antinodes sit a third of the way along, when that is a whole cell
+                    for num in [p1 * 2 + p2, p1 + p2 * 2] {
+                        if num.x % 3 == 0 && num.y % 3 == 0 {
+                            let c = Point::new(num.x / 3, num.y / 3);
+                            if map.contains(c) {
+                                antinodes_part1.insert(c);
+                            }
+                        }
+                    }
+
+                    // External antinodes
+                    for c in [p1 * 2 - p2, p2 * 2 - p1] {
+                        if map.contains(c) {
+                            antinodes_part1.insert(c);
+                        }
+                    }
+                }
+            }
+        }
+
+        antinodes_part1.len().into()
+    }
+
+    fn part2(AntennaMap { map, antennas }: &Self::Input) -> Answer {
+        let mut antinodes_part2 = HashSet::new();
+
+        // For each frequency
+        for positions in antennas.values() {
+            if positions.len() < 2 {
+                continue; // Only one antenna of this frequency
+            }
+
+            // Include the antenna positions themselves
+            antinodes_part2.extend(positions.iter().copied());
+
+            // For each pair of antennas
+            for (i, &p1) in positions.iter().enumerate() {
+                for &p2 in &positions[i + 1..] {
+                    let d = p2 - p1;
+                    let gcd = gcd(d.x, d.y);
+                    let step = Point::new(d.x / gcd, d.y / gcd);
+
+                    // Backwards and forwards until leaving the map
+                    antinodes_part2.extend(map.ray(p1 - step, -step).map(|(p, _)| p));
+                    antinodes_part2.extend(map.ray(p1 + step, step).map(|(p, _)| p));
+                }
+            }
+        }
+
+        antinodes_part2.len().into()
+    }
+}
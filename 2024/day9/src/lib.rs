@@ -0,0 +1,133 @@
+use aoc_core::{Answer, ParseError, Solution};
+
+pub struct Day9;
+
+// Parse the disk map into lengths of files and free spaces
+fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let disk_map = input.trim_end();
+    if disk_map.contains('\n') {
+        return Err(ParseError::new("the disk map must be a single line"));
+    }
+
+    let mut lengths = Vec::new();
+    for (i, c) in disk_map.chars().enumerate() {
+        let n = c.to_digit(10).ok_or_else(|| {
+            ParseError::at(1, i + 1, format!("expected a length digit, found `{}`", c))
+        })?;
+        lengths.push(n as usize);
+    }
+    Ok(lengths)
+}
+
+impl Solution for Day9 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 9;
+
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(lengths: &Self::Input) -> Answer {
+        // Build the initial disk blocks
+        let mut blocks = Vec::new();
+        let mut file_id = 0;
+        let mut is_file = true;
+        for length in lengths {
+            if is_file {
+                for _ in 0..*length {
+                    blocks.push(Some(file_id));
+                }
+                file_id += 1;
+            } else {
+                for _ in 0..*length {
+                    blocks.push(None);
+                }
+            }
+            is_file = !is_file;
+        }
+
+        // Move file blocks one at a time from the end to the leftmost free space
+        while let Some(free_pos) = blocks.iter().position(|&b| b.is_none()) {
+            if let Some(last_file_pos) = blocks.iter().rposition(|&b| b.is_some()) {
+                if last_file_pos < free_pos {
+                    break;
+                }
+                blocks[free_pos] = blocks[last_file_pos];
+                blocks[last_file_pos] = None;
+            } else {
+                break;
+            }
+        }
+
+        // Calculate the filesystem checksum for Part One
+        let mut checksum = 0;
+        for (position, &block) in blocks.iter().enumerate() {
+            if let Some(file_id) = block {
+                checksum += position * file_id as usize;
+            }
+        }
+
+        checksum.into()
+    }
+
+    fn part2(lengths: &Self::Input) -> Answer {
+        // Rebuild the initial disk blocks and record files
+        let mut blocks = Vec::new();
+        let mut file_id = 0;
+        let mut is_file = true;
+        let mut files = Vec::new(); // (file_id, start_pos, length)
+        let mut index = 0;
+        for length in lengths {
+            if is_file {
+                files.push((file_id, index, *length));
+                for _ in 0..*length {
+                    blocks.push(Some(file_id));
+                    index += 1;
+                }
+                file_id += 1;
+            } else {
+                for _ in 0..*length {
+                    blocks.push(None);
+                    index += 1;
+                }
+            }
+            is_file = !is_file;
+        }
+
+        // Move files in decreasing file ID order
+        for &(file_id, orig_pos, length) in files.iter().rev() {
+            let mut new_pos = None;
+            for i in 0..orig_pos {
+                if i + length > orig_pos {
+                    break;
+                }
+                if blocks[i..i + length].iter().all(|&b| b.is_none()) {
+                    new_pos = Some(i);
+                    break;
+                }
+            }
+            if let Some(pos) = new_pos {
+                // Remove the file from its original position
+                for block in &mut blocks[orig_pos..orig_pos + length] {
+                    *block = None;
+                }
+                // Place the file at the new position
+                for block in &mut blocks[pos..pos + length] {
+                    *block = Some(file_id);
+                }
+            }
+        }
+
+        // Calculate the filesystem checksum for Part Two
+        let mut checksum = 0;
+        for (position, &block) in blocks.iter().enumerate() {
+            if let Some(file_id) = block {
+                checksum += position * file_id as usize;
+            }
+        }
+
+        checksum.into()
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A problem with the puzzle input, with the place it was found when known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: Option<Position>,
+    pub message: String,
+}
+
+/// A 1-based line and column (in characters) within the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ParseError {
+    /// An error about the input as a whole, such as a missing section.
+    pub fn new(message: impl Into<String>) -> ParseError {
+        ParseError {
+            position: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            position: Some(Position { line, column }),
+            message: message.into(),
+        }
+    }
+
+    /// An error pointing at `token`, which must be a slice of `line`, the
+    /// `line_no`th line of the input.
+    pub fn in_line(
+        line_no: usize,
+        line: &str,
+        token: &str,
+        message: impl Into<String>,
+    ) -> ParseError {
+        ParseError::at(line_no, column_of(line, token), message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.position {
+            Some(Position { line, column }) => {
+                write!(f, "line {}, column {}: {}", line, column, self.message)
+            }
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// 1-based character column where `token` starts inside `line`.
+fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|&offset| offset <= line.len())
+        .expect("token must be a slice of line");
+    line[..offset].chars().count() + 1
+}
+
+/// The input's lines numbered from 1, for error positions.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(i, line)| (i + 1, line))
+}
+
+/// Parses `token`, a slice of `line`, reporting its position on failure.
+///
+/// `what` names the expected value, e.g. "a page number".
+pub fn parse_field<T: FromStr>(
+    line_no: usize,
+    line: &str,
+    token: &str,
+    what: &str,
+) -> Result<T, ParseError> {
+    token.parse().map_err(|_| {
+        let found = if token.is_empty() {
+            "nothing".to_string()
+        } else {
+            format!("`{}`", token)
+        };
+        ParseError::in_line(
+            line_no,
+            line,
+            token,
+            format!("expected {}, found {}", what, found),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_field_points_at_token() {
+        let line = "190: 10 x9";
+        let token = line.split_whitespace().last().unwrap();
+        let err = parse_field::<i64>(3, line, token, "a number").unwrap_err();
+        assert_eq!(err, ParseError::at(3, 9, "expected a number, found `x9`"));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 9: expected a number, found `x9`"
+        );
+    }
+
+    #[test]
+    fn test_column_counts_characters() {
+        let line = "é|z";
+        let token = &line[3..];
+        assert_eq!(column_of(line, token), 3);
+        assert_eq!(column_of(line, &line[line.len()..]), 4);
+    }
+
+    #[test]
+    fn test_whole_input_errors_have_no_position() {
+        assert_eq!(ParseError::new("no guard").to_string(), "no guard");
+    }
+}
@@ -0,0 +1,169 @@
+mod error;
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+pub use error::{numbered_lines, parse_field, ParseError, Position};
+
+/// A solved Advent of Code day.
+///
+/// Each day crate implements this on a unit struct so the runner can call
+/// every puzzle the same way. Parsing is separate from solving so the two
+/// can be timed independently.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// The parsed puzzle input shared by both parts.
+    type Input;
+
+    /// Rejects malformed input rather than guessing, so a truncated file or
+    /// another day's input fails loudly instead of giving a wrong answer.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// A puzzle answer. Most are integers; a few puzzles answer with text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64, usize, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Reads an answer back from text: anything that looks like an integer is a
+/// [`Answer::Number`], everything else is [`Answer::Text`].
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Answer, Infallible> {
+        Ok(match s.parse() {
+            Ok(n) => Answer::Number(n),
+            Err(_) => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// One part's answer along with how long parsing and solving took.
+#[derive(Debug, Clone)]
+pub struct Timed {
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timed {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Type-erased handle to a [`Solution`], used by the runner's registry.
+///
+/// Each part parses the raw input itself, so either can be run (and timed)
+/// on its own.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub part1: fn(&str) -> Result<Timed, ParseError>,
+    pub part2: fn(&str) -> Result<Timed, ParseError>,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Day {
+        Day {
+            year: S::YEAR,
+            day: S::DAY,
+            part1: run_part::<S, 1>,
+            part2: run_part::<S, 2>,
+        }
+    }
+
+    /// Runs part 1 or 2 on the raw input.
+    pub fn run(&self, part: u8, input: &str) -> Result<Timed, ParseError> {
+        match part {
+            1 => (self.part1)(input),
+            2 => (self.part2)(input),
+            _ => panic!("Advent of Code puzzles have two parts, not {}", part),
+        }
+    }
+}
+
+fn run_part<S: Solution, const PART: u8>(input: &str) -> Result<Timed, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = if PART == 1 {
+        S::part1(&parsed)
+    } else {
+        S::part2(&parsed)
+    };
+    let solve = start.elapsed();
+
+    Ok(Timed {
+        answer,
+        parse,
+        solve,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_conversions() {
+        assert_eq!(Answer::from(42usize), Answer::Number(42));
+        assert_eq!(Answer::from(-7i32), Answer::Number(-7));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".to_string()));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+    }
+
+    #[test]
+    fn test_answer_round_trips_through_text() {
+        for answer in [Answer::Number(-12), Answer::from("6,2,1")] {
+            assert_eq!(answer.to_string().parse(), Ok(answer));
+        }
+        assert_eq!("0042".parse(), Ok(Answer::Number(42)));
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_core::Answer;
+
+use crate::input;
+
+/// Recorded answers, keyed by `(year, day, part)`.
+///
+/// The file is TOML with one table per day:
+///
+/// ```toml
+/// [2024.day7]
+/// part1 = "3749"
+/// part2 = "11387"
+/// ```
+///
+/// Answers may be written as strings or integers. Strings that hold an
+/// integer are read as numbers, so `"3749"` and `3749` are the same answer.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u16, u8, u8), Answer>,
+}
+
+/// Reads an answer written as a TOML string or integer.
+pub fn answer_from_toml(value: &toml::Value) -> Option<Answer> {
+    match value {
+        toml::Value::String(s) => s.parse().ok(),
+        toml::Value::Integer(n) => Some(Answer::from(*n)),
+        _ => None,
+    }
+}
+
+pub fn default_path() -> PathBuf {
+    input::repo_root().join("answers.toml")
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        Answers::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e: toml::de::Error| e.message().to_string())?;
+        let mut answers = HashMap::new();
+
+        for (year_key, days) in &table {
+            let year = year_key
+                .parse()
+                .map_err(|_| format!("`{}` is not a year", year_key))?;
+            let days = days
+                .as_table()
+                .ok_or_else(|| format!("[{}] must be a table of days", year_key))?;
+
+            for (day_key, parts) in days {
+                let day = day_key
+                    .strip_prefix("day")
+                    .and_then(|n| n.parse().ok())
+                    .ok_or_else(|| {
+                        format!("`{}.{}` is not a day (expected `dayN`)", year_key, day_key)
+                    })?;
+                let parts = parts.as_table().ok_or_else(|| {
+                    format!("[{}.{}] must be a table of parts", year_key, day_key)
+                })?;
+
+                for (part_key, value) in parts {
+                    let part = match part_key.as_str() {
+                        "part1" => 1,
+                        "part2" => 2,
+                        _ => {
+                            return Err(format!(
+                                "unknown key `{}.{}.{}`",
+                                year_key, day_key, part_key
+                            ))
+                        }
+                    };
+                    let answer = answer_from_toml(value).ok_or_else(|| {
+                        format!(
+                            "`{}.{}.{}` must be a string or integer",
+                            year_key, day_key, part_key
+                        )
+                    })?;
+                    answers.insert((year, day, part), answer);
+                }
+            }
+        }
+
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(year, day, part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_strings_and_integers() {
+        let answers = Answers::parse(
+            r#"
+            [2024.day7]
+            part1 = "3749"
+            part2 = 11387
+
+            [2024.day8]
+            part1 = "ABC"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(answers.get(2024, 7, 1), Some(&Answer::Number(3749)));
+        assert_eq!(answers.get(2024, 7, 2), Some(&Answer::Number(11387)));
+        assert_eq!(answers.get(2024, 8, 1), Some(&Answer::from("ABC")));
+        assert_eq!(answers.get(2024, 8, 2), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        assert!(Answers::parse("[2024.day7]\npart3 = \"1\"").is_err());
+        assert!(Answers::parse("[2024.seven]\npart1 = \"1\"").is_err());
+        assert!(Answers::parse("[twenty.day7]\npart1 = \"1\"").is_err());
+    }
+
+    #[test]
+    fn test_repo_answers_file_parses() {
+        Answers::load(&default_path()).unwrap();
+    }
+}
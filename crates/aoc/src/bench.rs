@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use aoc_core::{Day, ParseError};
+
+use crate::cli::InputSource;
+use crate::input;
+use crate::timing::{format_duration, Stats};
+
+struct PartBench {
+    day: u8,
+    part: u8,
+    parse: Duration,
+    solve: Duration,
+    total: Stats,
+}
+
+fn bench_part(
+    day: &Day,
+    part: u8,
+    input: &str,
+    iterations: usize,
+) -> Result<PartBench, ParseError> {
+    let runs = (0..iterations)
+        .map(|_| day.run(part, input))
+        .collect::<Result<Vec<_>, _>>()?;
+    let totals: Vec<Duration> = runs.iter().map(|r| r.total()).collect();
+    // In f64, as `iterations` need not fit the u32 a `Duration` divides by
+    let mean = |f: fn(&aoc_core::Timed) -> Duration| {
+        runs.iter()
+            .map(f)
+            .sum::<Duration>()
+            .div_f64(iterations as f64)
+    };
+
+    Ok(PartBench {
+        day: day.day,
+        part,
+        parse: mean(|r| r.parse),
+        solve: mean(|r| r.solve),
+        total: Stats::of(&totals),
+    })
+}
+
+/// Runs each part `iterations` times, then prints per-part statistics and
+/// how much of `budget` every day uses.
+pub fn bench(days: &[Day], iterations: usize, budget: Duration) -> Result<(), String> {
+    let mut results = Vec::new();
+    for day in days {
+        let input = input::read(day, &InputSource::Default)?;
+        for part in 1..=2 {
+            let result = bench_part(day, part, &input, iterations)
+                .map_err(|e| format!("{}: {}", input::describe(day, &InputSource::Default), e))?;
+            results.push(result);
+        }
+    }
+
+    println!("{} iteration(s) per part", iterations);
+    println!(
+        "{:>3} {:>4} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "day", "part", "parse", "solve", "mean", "median", "stddev"
+    );
+    for r in &results {
+        println!(
+            "{:>3} {:>4} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            r.day,
+            r.part,
+            format_duration(r.parse),
+            format_duration(r.solve),
+            format_duration(r.total.mean),
+            format_duration(r.total.median),
+            format_duration(r.total.stddev),
+        );
+    }
+
+    println!();
+    println!("budget {}", format_duration(budget));
+    println!(
+        "{:>3} {:>10} {:>7} {:>10}",
+        "day", "mean", "share", "cumulative"
+    );
+    let mut cumulative = Duration::ZERO;
+    for pair in results.chunks(2) {
+        let day_total: Duration = pair.iter().map(|r| r.total.mean).sum();
+        cumulative += day_total;
+        println!(
+            "{:>3} {:>10} {:>6.1}% {:>10}",
+            pair[0].day,
+            format_duration(day_total),
+            share(day_total, budget),
+            format_duration(cumulative),
+        );
+    }
+    println!(
+        "total {:>8} {:>6.1}%{}",
+        format_duration(cumulative),
+        share(cumulative, budget),
+        if cumulative > budget {
+            "  OVER BUDGET"
+        } else {
+            ""
+        }
+    );
+    Ok(())
+}
+
+fn share(part: Duration, whole: Duration) -> f64 {
+    100.0 * part.as_secs_f64() / whole.as_secs_f64()
+}
@@ -0,0 +1,454 @@
+use std::path::PathBuf;
+
+use log::LevelFilter;
+
+pub const USAGE: &str = "\
+Usage:
+    aoc run <year> <day> [--input <path> | --example <n>] [--format <format>]
+    aoc run <year> --all [--example <n>] [--format <format>]
+    aoc verify [<year> [<day>]] [--answers <path> | --examples]
+    aoc bench <year> [<day> | --all] [--iterations <n>] [--budget <ms>]
+    aoc fetch <year> <day> [--refresh]
+    aoc -h | --help
+
+Logging (any command, anywhere on the line):
+    -v, --verbose    print each day's debug diagnostics to stderr
+    --trace          also print step-by-step trace output
+    (default)        print only answers; warnings still go to stderr
+
+Input:
+    --input <path>   read the puzzle input from <path> (`-` for stdin)
+    --example <n>    read example<n>.txt from the day's directory
+    (default)        read input.txt from the day's directory
+
+Output:
+    --format text    one block per day with timings (default)
+    --format json    one JSON object per part (JSON lines)
+    --format tsv     tab-separated, with a header row
+    JSON and TSV records hold year, day, part, answer and elapsed_ns.
+
+Verify:
+    Runs each selected day on its input.txt and compares both parts with
+    answers.toml at the repo root (or --answers <path>). Exits non-zero if
+    any answer differs. With --examples, runs each day's exampleN.txt
+    files instead and compares with the answers in its examples.toml.
+
+Bench:
+    Runs each part of the selected days <n> times (default 10) on its
+    input.txt and reports parse/solve means, mean/median/stddev of the
+    total, and each day's share of a year budget (default 1000 ms).
+
+Fetch:
+    Downloads the day's input and puzzle page into the cache (.aoc-cache
+    at the repo root, or $AOC_CACHE_DIR), serving repeats from the cache,
+    and copies the input to the day's input.txt if there is none yet.
+    Needs AOC_SESSION in the environment or .env; --refresh re-downloads
+    the puzzle page (part two appears once part one is solved).";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        year: u16,
+        days: DaySelection,
+        input: InputSource,
+        format: Format,
+    },
+    Verify {
+        year: Option<u16>,
+        days: DaySelection,
+        answers: Option<PathBuf>,
+        examples: bool,
+    },
+    Bench {
+        year: u16,
+        days: DaySelection,
+        iterations: usize,
+        budget_ms: u64,
+    },
+    Fetch {
+        year: u16,
+        day: u8,
+        refresh: bool,
+    },
+    /// Print [`USAGE`].
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DaySelection {
+    One(u8),
+    All,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    /// `input.txt` next to the day's solution.
+    Default,
+    /// `example<n>.txt` next to the day's solution.
+    Example(u32),
+    Path(PathBuf),
+    Stdin,
+}
+
+/// How `aoc run` prints its results.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+/// Removes the logging flags from `args`, returning the rest and the level
+/// they ask for.
+pub fn split_log_level<I>(args: I) -> (Vec<String>, LevelFilter)
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut level = LevelFilter::Warn;
+    let mut rest = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            "-v" | "--verbose" => level = level.max(LevelFilter::Debug),
+            "--trace" => level = LevelFilter::Trace,
+            _ => rest.push(arg),
+        }
+    }
+    (rest, level)
+}
+
+pub fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => parse_run(args),
+        Some("verify") => parse_verify(args),
+        Some("bench") => parse_bench(args),
+        Some("fetch") => parse_fetch(args),
+        Some("-h" | "--help") => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command `{}`", other)),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut year = None;
+    let mut days = None;
+    let mut input = InputSource::Default;
+    let mut format = Format::Text;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => days = Some(DaySelection::All),
+            "--input" => {
+                input = match flag_value(&mut args, "--input")?.as_str() {
+                    "-" => InputSource::Stdin,
+                    path => InputSource::Path(PathBuf::from(path)),
+                }
+            }
+            "--example" => {
+                let n = flag_value(&mut args, "--example")?;
+                input = InputSource::Example(parse_number(&n, "example number")?);
+            }
+            "--format" => {
+                format = match flag_value(&mut args, "--format")?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "tsv" => Format::Tsv,
+                    other => return Err(format!("unknown format `{}`", other)),
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown flag `{}`", arg)),
+            _ if year.is_none() => year = Some(parse_number(&arg, "year")?),
+            _ if days.is_none() => days = Some(DaySelection::One(parse_number(&arg, "day")?)),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let year = year.ok_or("missing year")?;
+    let days = days.ok_or("missing day (or --all)")?;
+    if days == DaySelection::All && matches!(input, InputSource::Path(_) | InputSource::Stdin) {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    Ok(Command::Run {
+        year,
+        days,
+        input,
+        format,
+    })
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut year = None;
+    let mut days = DaySelection::All;
+    let mut answers = None;
+    let mut examples = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = Some(PathBuf::from(flag_value(&mut args, "--answers")?)),
+            "--examples" => examples = true,
+            _ if arg.starts_with("--") => return Err(format!("unknown flag `{}`", arg)),
+            _ if year.is_none() => year = Some(parse_number(&arg, "year")?),
+            _ if days == DaySelection::All => days = DaySelection::One(parse_number(&arg, "day")?),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    if examples && answers.is_some() {
+        return Err("--answers cannot be used with --examples".to_string());
+    }
+    Ok(Command::Verify {
+        year,
+        days,
+        answers,
+        examples,
+    })
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut year = None;
+    let mut days = DaySelection::All;
+    let mut iterations = 10;
+    let mut budget_ms = 1000;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => days = DaySelection::All,
+            "--iterations" => {
+                let n = flag_value(&mut args, "--iterations")?;
+                iterations = parse_number(&n, "iteration count")?;
+                if iterations == 0 {
+                    return Err("--iterations must be at least 1".to_string());
+                }
+            }
+            "--budget" => {
+                budget_ms = parse_number(&flag_value(&mut args, "--budget")?, "budget")?;
+                if budget_ms == 0 {
+                    return Err("--budget must be at least 1 ms".to_string());
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown flag `{}`", arg)),
+            _ if year.is_none() => year = Some(parse_number(&arg, "year")?),
+            _ if days == DaySelection::All => days = DaySelection::One(parse_number(&arg, "day")?),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let year = year.ok_or("missing year")?;
+    Ok(Command::Bench {
+        year,
+        days,
+        iterations,
+        budget_ms,
+    })
+}
+
+fn parse_fetch(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut year = None;
+    let mut day = None;
+    let mut refresh = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--refresh" => refresh = true,
+            _ if arg.starts_with("--") => return Err(format!("unknown flag `{}`", arg)),
+            _ if year.is_none() => year = Some(parse_number(&arg, "year")?),
+            _ if day.is_none() => day = Some(parse_number(&arg, "day")?),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let year = year.ok_or("missing year")?;
+    let day = day.ok_or("missing day")?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("day must be 1-25, got {}", day));
+    }
+    Ok(Command::Fetch { year, day, refresh })
+}
+
+fn flag_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next().ok_or_else(|| format!("{} needs a value", flag))
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, what: &str) -> Result<T, String> {
+    arg.parse()
+        .map_err(|_| format!("invalid {} `{}`", what, arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_split_log_level() {
+        assert_eq!(
+            split_log_level(args("run 2024 7")),
+            (args("run 2024 7"), LevelFilter::Warn)
+        );
+        assert_eq!(
+            split_log_level(args("-v run 2024 7")),
+            (args("run 2024 7"), LevelFilter::Debug)
+        );
+        assert_eq!(
+            split_log_level(args("run 2024 --trace 7 --verbose")),
+            (args("run 2024 7"), LevelFilter::Trace)
+        );
+    }
+
+    #[test]
+    fn test_run_single_day() {
+        assert_eq!(
+            parse(args("run 2024 7")),
+            Ok(Command::Run {
+                year: 2024,
+                days: DaySelection::One(7),
+                input: InputSource::Default,
+                format: Format::Text,
+            })
+        );
+    }
+
+    #[test]
+    fn test_run_all_days() {
+        assert_eq!(
+            parse(args("run 2024 --all")),
+            Ok(Command::Run {
+                year: 2024,
+                days: DaySelection::All,
+                input: InputSource::Default,
+                format: Format::Text,
+            })
+        );
+    }
+
+    #[test]
+    fn test_run_requires_day() {
+        assert!(parse(args("run 2024")).is_err());
+        assert!(parse(args("run 2024 seven")).is_err());
+    }
+
+    #[test]
+    fn test_run_input_sources() {
+        let input_of = |line| match parse(args(line)) {
+            Ok(Command::Run { input, .. }) => input,
+            other => panic!("unexpected parse result {:?}", other),
+        };
+        assert_eq!(input_of("run 2024 7 --input -"), InputSource::Stdin);
+        assert_eq!(
+            input_of("run 2024 7 --input other/day7.txt"),
+            InputSource::Path(PathBuf::from("other/day7.txt"))
+        );
+        assert_eq!(input_of("run 2024 --example 2 7"), InputSource::Example(2));
+        assert_eq!(
+            input_of("run 2024 --all --example 1"),
+            InputSource::Example(1)
+        );
+    }
+
+    #[test]
+    fn test_run_formats() {
+        let format_of = |line| match parse(args(line)) {
+            Ok(Command::Run { format, .. }) => format,
+            other => panic!("unexpected parse result {:?}", other),
+        };
+        assert_eq!(format_of("run 2024 7 --format json"), Format::Json);
+        assert_eq!(format_of("run 2024 --all --format tsv"), Format::Tsv);
+        assert_eq!(format_of("run 2024 --format text --all"), Format::Text);
+        assert!(parse(args("run 2024 7 --format yaml")).is_err());
+        assert!(parse(args("run 2024 7 --format")).is_err());
+    }
+
+    #[test]
+    fn test_verify_selection() {
+        assert_eq!(
+            parse(args("verify")),
+            Ok(Command::Verify {
+                year: None,
+                days: DaySelection::All,
+                answers: None,
+                examples: false,
+            })
+        );
+        assert_eq!(
+            parse(args("verify 2024 9 --answers a.toml")),
+            Ok(Command::Verify {
+                year: Some(2024),
+                days: DaySelection::One(9),
+                answers: Some(PathBuf::from("a.toml")),
+                examples: false,
+            })
+        );
+        assert_eq!(
+            parse(args("verify 2024 --examples")),
+            Ok(Command::Verify {
+                year: Some(2024),
+                days: DaySelection::All,
+                answers: None,
+                examples: true,
+            })
+        );
+        assert!(parse(args("verify 2024 9 10")).is_err());
+        assert!(parse(args("verify --examples --answers a.toml")).is_err());
+    }
+
+    #[test]
+    fn test_bench_options() {
+        assert_eq!(
+            parse(args("bench 2024")),
+            Ok(Command::Bench {
+                year: 2024,
+                days: DaySelection::All,
+                iterations: 10,
+                budget_ms: 1000,
+            })
+        );
+        assert_eq!(
+            parse(args("bench 2024 6 --iterations 3 --budget 250")),
+            Ok(Command::Bench {
+                year: 2024,
+                days: DaySelection::One(6),
+                iterations: 3,
+                budget_ms: 250,
+            })
+        );
+        assert!(parse(args("bench 2024 --iterations 0")).is_err());
+        assert!(parse(args("bench 2024 --budget 0")).is_err());
+        assert!(parse(args("bench 2024 --budget -5")).is_err());
+        assert!(parse(args("bench --all")).is_err());
+    }
+
+    #[test]
+    fn test_help() {
+        assert_eq!(parse(args("--help")), Ok(Command::Help));
+        assert_eq!(parse(args("-h")), Ok(Command::Help));
+        assert!(parse(args("--version")).is_err());
+    }
+
+    #[test]
+    fn test_fetch() {
+        assert_eq!(
+            parse(args("fetch 2024 12 --refresh")),
+            Ok(Command::Fetch {
+                year: 2024,
+                day: 12,
+                refresh: true,
+            })
+        );
+        assert!(parse(args("fetch 2024")).is_err());
+        assert!(parse(args("fetch 2024 26")).is_err());
+    }
+
+    #[test]
+    fn test_run_rejects_bad_input_flags() {
+        assert!(parse(args("run 2024 7 --input")).is_err());
+        assert!(parse(args("run 2024 7 --example x")).is_err());
+        assert!(parse(args("run 2024 --all --input -")).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+use aoc_core::Day;
+
+/// Every solved day, in calendar order.
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::of::<day1::Day1>(),
+        Day::of::<day2::Day2>(),
+        Day::of::<day3::Day3>(),
+        Day::of::<day4::Day4>(),
+        Day::of::<day5::Day5>(),
+        Day::of::<day6::Day6>(),
+        Day::of::<day7::Day7>(),
+        Day::of::<day8::Day8>(),
+        Day::of::<day9::Day9>(),
+        Day::of::<day10::Day10>(),
+        Day::of::<day11::Day11>(),
+    ]
+}
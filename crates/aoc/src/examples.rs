@@ -0,0 +1,124 @@
+use std::fs;
+use std::io;
+
+use aoc_core::{Answer, Day};
+
+use crate::answers::answer_from_toml;
+use crate::cli::InputSource;
+use crate::input;
+use crate::verify::{self, Status, Summary};
+
+/// The answers a day's puzzle text gives for its worked examples, read from
+/// `examples.toml` in the day's directory:
+///
+/// ```toml
+/// [example1]
+/// part1 = 1928
+/// part2 = 2858
+/// ```
+///
+/// Each `[exampleN]` table refers to `exampleN.txt`. The files are written by
+/// `scripts/extract_examples.py`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Fixture {
+    /// `(example number, part, expected answer)`, by example then part.
+    pub expected: Vec<(u32, u8, Answer)>,
+}
+
+impl Fixture {
+    /// The day's fixture, or `None` if it has no `examples.toml`.
+    pub fn load(day: &Day) -> Result<Option<Fixture>, String> {
+        let path = input::day_dir(day).join("examples.toml");
+        match fs::read_to_string(&path) {
+            Ok(text) => Fixture::parse(&text)
+                .map(Some)
+                .map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Fixture, String> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e: toml::de::Error| e.message().to_string())?;
+        let mut expected = Vec::new();
+
+        for (example_key, parts) in &table {
+            let example = example_key
+                .strip_prefix("example")
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| {
+                    format!("`{}` is not an example (expected `exampleN`)", example_key)
+                })?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("[{}] must be a table of parts", example_key))?;
+
+            for (part_key, value) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(format!("unknown key `{}.{}`", example_key, part_key)),
+                };
+                let answer = answer_from_toml(value).ok_or_else(|| {
+                    format!("`{}.{}` must be a string or integer", example_key, part_key)
+                })?;
+                expected.push((example, part, answer));
+            }
+        }
+
+        expected.sort_by_key(|&(example, part, _)| (example, part));
+        Ok(Fixture { expected })
+    }
+}
+
+/// Runs every day with a fixture on its examples and compares the answers.
+pub fn verify(days: &[Day]) -> Result<Summary, String> {
+    let mut summary = Summary::default();
+
+    for day in days {
+        let Some(fixture) = Fixture::load(day)? else {
+            continue;
+        };
+        for (example, part, expected) in &fixture.expected {
+            let status = match input::read(day, &InputSource::Example(*example)) {
+                Ok(input) => verify::compare(day, *part, &input, expected),
+                Err(_) => Status::Missing("no example file"),
+            };
+            println!(
+                "{} day {:>2} example{} part {}: {}",
+                day.year, day.day, example, part, status
+            );
+            summary.record(&status);
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_parse_fixture() {
+        let fixture =
+            Fixture::parse("[example2]\npart1 = 36\n\n[example1]\npart2 = \"abc\"\n").unwrap();
+        assert_eq!(
+            fixture.expected,
+            [(1, 2, Answer::from("abc")), (2, 1, Answer::Number(36))]
+        );
+        assert!(Fixture::parse("[sample1]\npart1 = 1").is_err());
+        assert!(Fixture::parse("[example1]\npart3 = 1").is_err());
+    }
+
+    #[test]
+    fn test_every_day_solves_its_examples() {
+        let summary = verify(&days::all()).unwrap();
+        assert_eq!(summary.failed, 0, "{}", summary);
+        assert_eq!(summary.missing, 0, "{}", summary);
+        assert!(summary.passed > 0);
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::input;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimum gap between two requests to the server, across runs.
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = concat!(
+    "aoc-runner/",
+    env!("CARGO_PKG_VERSION"),
+    " (personal Advent of Code solutions repo)"
+);
+
+/// Downloads puzzle inputs and prose, keeping a copy of everything it
+/// fetches so each file is requested from the server at most once.
+///
+/// Cached files live at `<cache>/<year>/day<N>/{input.txt,prose.html}`.
+pub struct Fetcher {
+    base_url: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+    agent: ureq::Agent,
+}
+
+/// Where a fetched file came from.
+#[derive(Debug, PartialEq)]
+pub enum Origin {
+    Cache,
+    Server,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: Option<String>, cache_dir: PathBuf) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            cache_dir,
+            min_interval: MIN_INTERVAL,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Configures a fetcher from the environment, falling back to `.env` at
+    /// the repo root:
+    ///
+    /// - `AOC_SESSION`: the `session` cookie of a logged-in browser
+    /// - `AOC_BASE_URL`: server to talk to (default `https://adventofcode.com`)
+    /// - `AOC_CACHE_DIR`: cache directory (default `<repo>/.aoc-cache`)
+    pub fn from_env() -> Result<Fetcher, String> {
+        let dotenv = read_dotenv(&input::repo_root().join(".env"))?;
+        let var = |key: &str| {
+            env::var(key)
+                .ok()
+                .or_else(|| dotenv.get(key).cloned())
+                .filter(|value| !value.is_empty())
+        };
+
+        let base_url = var("AOC_BASE_URL").unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let cache_dir = var("AOC_CACHE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| input::repo_root().join(".aoc-cache"));
+        Ok(Fetcher::new(&base_url, var("AOC_SESSION"), cache_dir))
+    }
+
+    pub fn cache_path(&self, year: u16, day: u8, file: &str) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day{}", day))
+            .join(file)
+    }
+
+    /// The personal puzzle input. Inputs never change, so a cached copy is
+    /// always used.
+    pub fn input(&self, year: u16, day: u8) -> Result<(String, Origin), String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        self.cached(&self.cache_path(year, day, "input.txt"), &url, false)
+    }
+
+    /// The puzzle page as HTML. Part two only appears once part one is
+    /// solved, so `refresh` re-downloads it.
+    pub fn prose(&self, year: u16, day: u8, refresh: bool) -> Result<(String, Origin), String> {
+        let url = format!("{}/{}/day/{}", self.base_url, year, day);
+        self.cached(&self.cache_path(year, day, "prose.html"), &url, refresh)
+    }
+
+    fn cached(&self, path: &Path, url: &str, refresh: bool) -> Result<(String, Origin), String> {
+        if !refresh {
+            if let Ok(text) = fs::read_to_string(path) {
+                return Ok((text, Origin::Cache));
+            }
+        }
+
+        let text = self.get(url)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        }
+        fs::write(path, &text).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        Ok((text, Origin::Server))
+    }
+
+    fn get(&self, url: &str) -> Result<String, String> {
+        let session = self
+            .session
+            .as_deref()
+            .ok_or("AOC_SESSION is not set (add `AOC_SESSION=<cookie>` to .env)")?;
+        self.wait_for_turn()?;
+
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", session))
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(400 | 401, _) => {
+                    format!(
+                        "{}: the session cookie was rejected (is AOC_SESSION current?)",
+                        url
+                    )
+                }
+                ureq::Error::Status(404, _) => {
+                    format!("{}: not found (is the puzzle unlocked yet?)", url)
+                }
+                e => format!("{}: {}", url, e),
+            })?;
+        response
+            .into_string()
+            .map_err(|e| format!("{}: cannot read response: {}", url, e))
+    }
+
+    // Sleeps until `min_interval` has passed since the last request made by
+    // any run sharing this cache, then records this one.
+    fn wait_for_turn(&self) -> Result<(), String> {
+        let stamp = self.cache_dir.join("last-request");
+        let last = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .map(|ms| UNIX_EPOCH + Duration::from_millis(ms));
+
+        if let Some(last) = last {
+            let elapsed = SystemTime::now().duration_since(last).unwrap_or_default();
+            if let Some(wait) = self.min_interval.checked_sub(elapsed) {
+                eprintln!("waiting {:.1}s between requests", wait.as_secs_f64());
+                thread::sleep(wait);
+            }
+        }
+
+        fs::create_dir_all(&self.cache_dir)
+            .map_err(|e| format!("cannot create {}: {}", self.cache_dir.display(), e))?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        fs::write(&stamp, now.to_string())
+            .map_err(|e| format!("cannot write {}: {}", stamp.display(), e))
+    }
+}
+
+// `KEY=value` lines; blank lines and `#` comments are skipped and values may
+// be quoted. A missing file is the same as an empty one.
+fn read_dotenv(path: &Path) -> Result<HashMap<String, String>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+    };
+    Ok(parse_dotenv(&text))
+}
+
+fn parse_dotenv(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.strip_prefix("export ").unwrap_or(line).split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let unquoted = ['"', '\'']
+                .iter()
+                .find_map(|&q| value.strip_prefix(q)?.strip_suffix(q))
+                .unwrap_or(value);
+            (key.trim().to_string(), unquoted.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    use super::*;
+
+    /// Serves `responses` in order, one per connection, and sends each
+    /// request's head back over the returned channel.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut head = String::new();
+                let mut reader = BufReader::new(&stream);
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                drop(reader);
+                tx.send(head).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (url, rx)
+    }
+
+    fn temp_cache(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn fetcher(url: &str, cache: &Path) -> Fetcher {
+        let mut fetcher = Fetcher::new(url, Some("abc123".to_string()), cache.to_path_buf());
+        fetcher.min_interval = Duration::ZERO;
+        fetcher
+    }
+
+    #[test]
+    fn test_input_is_fetched_once_then_cached() {
+        let (url, requests) = mock_server(vec![(200, "1 2 3\n")]);
+        let cache = temp_cache("input");
+        let fetcher = fetcher(&url, &cache);
+
+        assert_eq!(
+            fetcher.input(2024, 7),
+            Ok(("1 2 3\n".to_string(), Origin::Server))
+        );
+        let head = requests.recv().unwrap();
+        assert!(
+            head.starts_with("GET /2024/day/7/input HTTP/1.1"),
+            "{}",
+            head
+        );
+        assert!(head.contains("session=abc123"), "{}", head);
+        assert!(head.contains("aoc-runner/"), "{}", head);
+
+        // The mock server only answers once; a second request would fail
+        assert_eq!(
+            fetcher.input(2024, 7),
+            Ok(("1 2 3\n".to_string(), Origin::Cache))
+        );
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_prose_refresh_bypasses_cache() {
+        let (url, requests) = mock_server(vec![(200, "part one"), (200, "part two")]);
+        let cache = temp_cache("prose");
+        let fetcher = fetcher(&url, &cache);
+
+        assert_eq!(fetcher.prose(2024, 7, false).unwrap().0, "part one");
+        assert_eq!(fetcher.prose(2024, 7, false).unwrap().1, Origin::Cache);
+        assert_eq!(
+            fetcher.prose(2024, 7, true),
+            Ok(("part two".to_string(), Origin::Server))
+        );
+        assert_eq!(requests.iter().count(), 2);
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_server_errors_are_reported_and_not_cached() {
+        let (url, _requests) = mock_server(vec![(404, "not yet"), (400, "bad session")]);
+        let cache = temp_cache("errors");
+        let fetcher = fetcher(&url, &cache);
+
+        assert!(fetcher.input(2024, 25).unwrap_err().contains("not found"));
+        assert!(fetcher
+            .input(2024, 25)
+            .unwrap_err()
+            .contains("session cookie"));
+        assert!(!fetcher.cache_path(2024, 25, "input.txt").exists());
+        let _ = fs::remove_dir_all(&cache);
+    }
+
+    #[test]
+    fn test_requests_are_spaced_out() {
+        let (url, _requests) = mock_server(vec![(200, "a"), (200, "b")]);
+        let cache = temp_cache("rate");
+        let mut fetcher = fetcher(&url, &cache);
+        fetcher.min_interval = Duration::from_millis(200);
+
+        let start = Instant::now();
+        fetcher.input(2024, 1).unwrap();
+        fetcher.input(2024, 2).unwrap();
+        // The stamp is stored in whole milliseconds, so allow a little slack
+        assert!(start.elapsed() >= Duration::from_millis(190));
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_missing_session_only_matters_on_a_cache_miss() {
+        let cache = temp_cache("session");
+        let fetcher = Fetcher::new("http://127.0.0.1:9", None, cache.clone());
+        assert!(fetcher.input(2024, 1).unwrap_err().contains("AOC_SESSION"));
+
+        let path = fetcher.cache_path(2024, 1, "input.txt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "cached").unwrap();
+        assert_eq!(
+            fetcher.input(2024, 1),
+            Ok(("cached".to_string(), Origin::Cache))
+        );
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let vars = parse_dotenv(
+            "# session for adventofcode.com\n\
+             AOC_SESSION=\"53616c74\"\n\
+             export AOC_CACHE_DIR = /tmp/aoc\n\
+             \n\
+             EMPTY=\n",
+        );
+        assert_eq!(vars["AOC_SESSION"], "53616c74");
+        assert_eq!(vars["AOC_CACHE_DIR"], "/tmp/aoc");
+        assert_eq!(vars["EMPTY"], "");
+        assert_eq!(vars.len(), 3);
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use aoc_core::Day;
+
+use crate::cli::InputSource;
+
+/// The repository root, two levels above this crate, so the runner finds
+/// its files regardless of the current directory.
+pub fn repo_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .expect("runner crate lives two levels below the repo root")
+}
+
+/// Directory holding a day's solution and its input files,
+/// `<repo>/<year>/day<N>`.
+pub fn day_dir(day: &Day) -> PathBuf {
+    repo_root()
+        .join(day.year.to_string())
+        .join(format!("day{}", day.day))
+}
+
+pub fn path_for(day: &Day, source: &InputSource) -> Option<PathBuf> {
+    match source {
+        InputSource::Default => Some(day_dir(day).join("input.txt")),
+        InputSource::Example(n) => Some(day_dir(day).join(format!("example{}.txt", n))),
+        InputSource::Path(path) => Some(path.clone()),
+        InputSource::Stdin => None,
+    }
+}
+
+/// Names the input in error messages: its path, or `stdin`.
+pub fn describe(day: &Day, source: &InputSource) -> String {
+    match path_for(day, source) {
+        Some(path) => path.display().to_string(),
+        None => "stdin".to_string(),
+    }
+}
+
+pub fn read(day: &Day, source: &InputSource) -> Result<String, String> {
+    match path_for(day, source) {
+        Some(path) => {
+            fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))
+        }
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("cannot read stdin: {}", e))?;
+            Ok(input)
+        }
+    }
+}
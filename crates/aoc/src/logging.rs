@@ -0,0 +1,27 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Writes day diagnostics (`log::debug!`, `log::trace!`, ...) to stderr so
+/// they never mix with the answers on stdout.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+pub fn init(level: LevelFilter) {
+    // Only fails if a logger is already installed, which main never does twice
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
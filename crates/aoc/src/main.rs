@@ -0,0 +1,197 @@
+mod answers;
+mod bench;
+mod cli;
+mod days;
+mod examples;
+mod fetch;
+mod input;
+mod logging;
+mod output;
+mod timing;
+mod verify;
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use answers::Answers;
+use aoc_core::Day;
+use cli::{Command, DaySelection, Format, InputSource};
+use output::Printer;
+
+fn run_day(day: &Day, source: &InputSource, printer: &mut Printer) -> Result<(), String> {
+    let input = input::read(day, source)?;
+
+    let results = (1..=2)
+        .map(|part| day.run(part, &input))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("{}: {}", input::describe(day, source), e))?;
+
+    printer.day(day);
+    for (part, result) in (1..=2).zip(&results) {
+        printer.part(day, part, result);
+    }
+    Ok(())
+}
+
+// Registered days matching the selection; `None` means every year.
+fn select(year: Option<u16>, selection: &DaySelection) -> Result<Vec<Day>, String> {
+    let selected: Vec<Day> = days::all()
+        .into_iter()
+        .filter(|d| year.is_none_or(|year| d.year == year))
+        .filter(|d| match selection {
+            DaySelection::One(day) => d.day == *day,
+            DaySelection::All => true,
+        })
+        .collect();
+
+    if selected.is_empty() {
+        return Err(match (year, selection) {
+            (Some(year), DaySelection::One(day)) => format!("no solution for {} day {}", year, day),
+            (Some(year), DaySelection::All) => format!("no solutions for {}", year),
+            (None, _) => "no solutions registered".to_string(),
+        });
+    }
+    Ok(selected)
+}
+
+fn run(
+    year: u16,
+    selection: DaySelection,
+    source: InputSource,
+    format: Format,
+) -> Result<(), String> {
+    let selected = select(Some(year), &selection)?;
+    let mut printer = Printer::new(format);
+
+    for day in &selected {
+        // Only some days have a given example, so a full run skips the rest
+        if let (DaySelection::All, InputSource::Example(n)) = (&selection, &source) {
+            if input::path_for(day, &source).is_some_and(|path| !path.exists()) {
+                eprintln!(
+                    "{} day {}: no example{}.txt, skipping",
+                    day.year, day.day, n
+                );
+                continue;
+            }
+        }
+        run_day(day, &source, &mut printer)?;
+    }
+    Ok(())
+}
+
+fn verify(
+    year: Option<u16>,
+    selection: DaySelection,
+    answers: Option<PathBuf>,
+    examples: bool,
+) -> Result<(), String> {
+    let selected = select(year, &selection)?;
+
+    let summary = if examples {
+        examples::verify(&selected)?
+    } else {
+        let answers = Answers::load(&answers.unwrap_or_else(answers::default_path))?;
+        verify::verify(&selected, &answers)
+    };
+    println!("{}", summary);
+    if summary.failed > 0 {
+        return Err(format!(
+            "{} answer(s) differ from the expected ones",
+            summary.failed
+        ));
+    }
+    Ok(())
+}
+
+fn bench(
+    year: u16,
+    selection: DaySelection,
+    iterations: usize,
+    budget_ms: u64,
+) -> Result<(), String> {
+    let selected = select(Some(year), &selection)?;
+    bench::bench(&selected, iterations, Duration::from_millis(budget_ms))
+}
+
+fn fetch(year: u16, day: u8, refresh: bool) -> Result<(), String> {
+    let fetcher = fetch::Fetcher::from_env()?;
+    let report = |what: &str, origin: fetch::Origin, path: PathBuf| {
+        let how = match origin {
+            fetch::Origin::Cache => "cached",
+            fetch::Origin::Server => "downloaded",
+        };
+        println!("{} {}: {}", what, how, path.display());
+    };
+
+    let (text, origin) = fetcher.input(year, day)?;
+    report("input", origin, fetcher.cache_path(year, day, "input.txt"));
+
+    let day_dir = input::repo_root()
+        .join(year.to_string())
+        .join(format!("day{}", day));
+    let input_path = day_dir.join("input.txt");
+    if !input_path.exists() {
+        fs::create_dir_all(&day_dir)
+            .map_err(|e| format!("cannot create {}: {}", day_dir.display(), e))?;
+        fs::write(&input_path, text)
+            .map_err(|e| format!("cannot write {}: {}", input_path.display(), e))?;
+        println!("saved input to {}", input_path.display());
+    }
+
+    let (_, origin) = fetcher.prose(year, day, refresh)?;
+    report(
+        "puzzle",
+        origin,
+        fetcher.cache_path(year, day, "prose.html"),
+    );
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let (args, level) = cli::split_log_level(std::env::args().skip(1));
+    logging::init(level);
+
+    let command = match cli::parse(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match command {
+        Command::Run {
+            year,
+            days,
+            input,
+            format,
+        } => run(year, days, input, format),
+        Command::Verify {
+            year,
+            days,
+            answers,
+            examples,
+        } => verify(year, days, answers, examples),
+        Command::Bench {
+            year,
+            days,
+            iterations,
+            budget_ms,
+        } => bench(year, days, iterations, budget_ms),
+        Command::Fetch { year, day, refresh } => fetch(year, day, refresh),
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
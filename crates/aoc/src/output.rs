@@ -0,0 +1,153 @@
+use aoc_core::{Answer, Day, Timed};
+
+use crate::cli::Format;
+use crate::timing::format_duration;
+
+/// Writes `aoc run` results in the chosen [`Format`].
+///
+/// JSON and TSV emit one record per part so other tools can consume the
+/// results without scraping the human-readable text.
+pub struct Printer {
+    format: Format,
+    wrote_header: bool,
+}
+
+impl Printer {
+    pub fn new(format: Format) -> Printer {
+        Printer {
+            format,
+            wrote_header: false,
+        }
+    }
+
+    /// Called once per day before its parts.
+    pub fn day(&mut self, day: &Day) {
+        match self.format {
+            Format::Text => println!("{} day {}", day.year, day.day),
+            Format::Json => {}
+            Format::Tsv => {
+                if !self.wrote_header {
+                    println!("year\tday\tpart\tanswer\telapsed_ns");
+                    self.wrote_header = true;
+                }
+            }
+        }
+    }
+
+    pub fn part(&mut self, day: &Day, part: u8, result: &Timed) {
+        println!("{}", render(self.format, day, part, result));
+    }
+}
+
+fn render(format: Format, day: &Day, part: u8, result: &Timed) -> String {
+    match format {
+        Format::Text => format!(
+            "  part {}: {}  (parse {}, solve {})",
+            part,
+            result.answer,
+            format_duration(result.parse),
+            format_duration(result.solve),
+        ),
+        Format::Json => format!(
+            r#"{{"year":{},"day":{},"part":{},"answer":{},"elapsed_ns":{}}}"#,
+            day.year,
+            day.day,
+            part,
+            json_answer(&result.answer),
+            result.total().as_nanos(),
+        ),
+        Format::Tsv => format!(
+            "{}\t{}\t{}\t{}\t{}",
+            day.year,
+            day.day,
+            part,
+            tsv_field(&result.answer.to_string()),
+            result.total().as_nanos(),
+        ),
+    }
+}
+
+// Numbers stay JSON numbers; text answers become escaped strings.
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(s) => {
+            let mut out = String::from('"');
+            for c in s.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\r' => out.push_str("\\r"),
+                    '\t' => out.push_str("\\t"),
+                    c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+            out
+        }
+    }
+}
+
+// A tab or newline inside a field would break the row, so escape them.
+fn tsv_field(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn day7() -> Day {
+        Day {
+            year: 2024,
+            day: 7,
+            part1: |_| unreachable!(),
+            part2: |_| unreachable!(),
+        }
+    }
+
+    fn timed(answer: Answer) -> Timed {
+        Timed {
+            answer,
+            parse: Duration::from_nanos(1500),
+            solve: Duration::from_nanos(2500),
+        }
+    }
+
+    #[test]
+    fn test_render_records() {
+        let result = timed(Answer::Number(3749));
+        assert_eq!(
+            render(Format::Json, &day7(), 1, &result),
+            r#"{"year":2024,"day":7,"part":1,"answer":3749,"elapsed_ns":4000}"#
+        );
+        assert_eq!(
+            render(Format::Tsv, &day7(), 1, &result),
+            "2024\t7\t1\t3749\t4000"
+        );
+        assert_eq!(
+            render(Format::Text, &day7(), 2, &result),
+            "  part 2: 3749  (parse 1.5 µs, solve 2.5 µs)"
+        );
+    }
+
+    #[test]
+    fn test_text_answers_are_escaped() {
+        let result = timed(Answer::from("a\"b\\c\nd\te"));
+        assert_eq!(
+            render(Format::Json, &day7(), 2, &result),
+            r#"{"year":2024,"day":7,"part":2,"answer":"a\"b\\c\nd\te","elapsed_ns":4000}"#
+        );
+        assert_eq!(
+            render(Format::Tsv, &day7(), 2, &result),
+            "2024\t7\t2\ta\"b\\\\c\\nd\\te\t4000"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+/// Formats a duration with three significant-ish digits in a fitting unit.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos() as f64;
+    if nanos < 1e3 {
+        format!("{:.0} ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.1} µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2} ms", nanos / 1e6)
+    } else {
+        format!("{:.2} s", nanos / 1e9)
+    }
+}
+
+/// Summary statistics over repeated measurements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    /// Population standard deviation.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "statistics need at least one sample");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Stats {
+            mean: Duration::from_secs_f64(mean),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration_units() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3 µs");
+        assert_eq!(format_duration(Duration::from_micros(4_560)), "4.56 ms");
+        assert_eq!(format_duration(Duration::from_millis(1_230)), "1.23 s");
+    }
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::of(&[ms(4), ms(2), ms(6), ms(8)]);
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.median, ms(5));
+        // Population deviation of 2, 4, 6, 8 is sqrt(5)
+        assert_eq!(stats.stddev.as_micros(), 2236);
+
+        let odd = Stats::of(&[ms(1), ms(9), ms(3)]);
+        assert_eq!(odd.median, ms(3));
+    }
+}
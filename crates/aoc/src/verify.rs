@@ -0,0 +1,102 @@
+use std::fmt;
+
+use aoc_core::{Answer, Day, ParseError};
+
+use crate::answers::Answers;
+use crate::cli::InputSource;
+use crate::input;
+
+pub enum Status {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The day rejected its input.
+    Invalid(ParseError),
+    /// No recorded answer, or no local input to check it against.
+    Missing(&'static str),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Status::Invalid(e) => write!(f, "FAIL (invalid input: {})", e),
+            Status::Missing(why) => write!(f, "missing ({})", why),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Summary {
+    pub fn record(&mut self, status: &Status) {
+        match status {
+            Status::Pass => self.passed += 1,
+            Status::Fail { .. } | Status::Invalid(_) => self.failed += 1,
+            Status::Missing(_) => self.missing += 1,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} missing",
+            self.passed, self.failed, self.missing
+        )
+    }
+}
+
+fn check(day: &Day, input: Option<&str>, part: u8, answers: &Answers) -> Status {
+    let Some(expected) = answers.get(day.year, day.day, part) else {
+        return Status::Missing("no recorded answer");
+    };
+    let Some(input) = input else {
+        return Status::Missing("no input.txt");
+    };
+
+    compare(day, part, input, expected)
+}
+
+/// Runs one part on `input` and compares its answer with `expected`.
+pub fn compare(day: &Day, part: u8, input: &str, expected: &Answer) -> Status {
+    let actual = match day.run(part, input) {
+        Ok(result) => result.answer,
+        Err(e) => return Status::Invalid(e),
+    };
+    if actual == *expected {
+        Status::Pass
+    } else {
+        Status::Fail {
+            expected: expected.clone(),
+            actual,
+        }
+    }
+}
+
+/// Runs every day against its local input and compares with `answers`.
+pub fn verify(days: &[Day], answers: &Answers) -> Summary {
+    let mut summary = Summary::default();
+
+    for day in days {
+        let input = input::read(day, &InputSource::Default).ok();
+        for part in 1..=2 {
+            let status = check(day, input.as_deref(), part, answers);
+            println!("{} day {:>2} part {}: {}", day.year, day.day, part, status);
+            summary.record(&status);
+        }
+    }
+
+    summary
+}
@@ -0,0 +1,382 @@
+mod point;
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use aoc_core::ParseError;
+
+pub use point::Point;
+
+/// A rectangular grid stored row-major in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Returned when the rows of a grid's input do not all have the same width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeError {
+    /// Zero-based index of the first offending row.
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "row {} has {} cells, expected {}",
+            self.row + 1,
+            self.found,
+            self.expected
+        )
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// Points at the first cell past the shorter of the two widths: where the
+/// row ends early, or its first extra cell.
+impl From<ShapeError> for ParseError {
+    fn from(e: ShapeError) -> ParseError {
+        ParseError::at(
+            e.row + 1,
+            e.found.min(e.expected) + 1,
+            format!("row has {} cells, expected {}", e.found, e.expected),
+        )
+    }
+}
+
+impl Grid<char> {
+    /// Parses one row per line, one cell per character.
+    pub fn parse(input: &str) -> Result<Grid<char>, ShapeError> {
+        Grid::parse_with(input, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` x `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses one row per line, mapping each character through `f`.
+    ///
+    /// Line endings may be `\n` or `\r\n`; trailing blank lines are ignored.
+    pub fn parse_with<F>(input: &str, mut f: F) -> Result<Grid<T>, ShapeError>
+    where
+        F: FnMut(char) -> T,
+    {
+        let mut width = 0;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (row, line) in input.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let found = cells.len() - before;
+
+            if row == 0 {
+                width = found;
+            } else if found != width {
+                return Err(ShapeError { row, expected: width, found });
+            }
+            height += 1;
+        }
+
+        Ok(Grid { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    fn offset(&self, p: Point) -> Option<usize> {
+        if self.contains(p) {
+            Some(p.y as usize * self.width + p.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn point_at(&self, offset: usize) -> Point {
+        Point::from((offset % self.width, offset / self.width))
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Every in-bounds point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| Point::from((i % width, i / width)))
+    }
+
+    /// Every cell paired with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The in-bounds orthogonal neighbours of `p`.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Point::ORTHOGONAL
+            .into_iter()
+            .map(move |d| p + d)
+            .filter(|&n| self.contains(n))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbours of `p`.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Point::ALL_DIRECTIONS
+            .into_iter()
+            .map(move |d| p + d)
+            .filter(|&n| self.contains(n))
+    }
+
+    /// Walks from `start` in steps of `step` until leaving the grid.
+    pub fn ray(&self, start: Point, step: Point) -> Line<'_, T> {
+        assert!(step != Point::ZERO, "a ray needs a non-zero step");
+        Line { grid: self, next: start, step }
+    }
+
+    /// Every maximal line through the grid in direction `step`.
+    ///
+    /// Each line starts at a cell whose predecessor (`p - step`) is outside
+    /// the grid, so `lines(Point::RIGHT)` yields the rows and
+    /// `lines(Point::DOWN_RIGHT)` the diagonals.
+    pub fn lines(&self, step: Point) -> impl Iterator<Item = Line<'_, T>> {
+        self.points()
+            .filter(move |&p| !self.contains(p - step))
+            .map(move |p| self.ray(p, step))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> Line<'_, T> {
+        self.ray(Point::from((x, 0)), Point::DOWN)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero chunk size, which an empty grid would give.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Line<'_, T>> {
+        self.lines(Point::DOWN)
+    }
+
+    /// Top-left to bottom-right diagonals.
+    pub fn diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        self.lines(Point::DOWN_RIGHT)
+    }
+
+    /// Top-right to bottom-left diagonals.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        self.lines(Point::DOWN_LEFT)
+    }
+
+    /// Position of the first cell, row by row, matching `pred`.
+    pub fn find<P>(&self, pred: P) -> Option<Point>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells.iter().position(pred).map(|i| self.point_at(i))
+    }
+
+    /// Positions of every cell matching `pred`, row by row.
+    pub fn find_all<'a, P>(&'a self, mut pred: P) -> impl Iterator<Item = Point> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.iter().filter(move |(_, c)| pred(c)).map(|(p, _)| p)
+    }
+
+    /// Position of the first cell equal to `value`.
+    pub fn position_of(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.find(|c| c == value)
+    }
+
+    /// Builds a grid of the same shape by mapping every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.get(p) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside a {}x{} grid", p, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(p) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside a {}x{} grid", p, width, height),
+        }
+    }
+}
+
+/// Renders one row per line with no separator between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A straight walk through a [`Grid`], yielding each position and its cell.
+#[derive(Clone)]
+pub struct Line<'a, T> {
+    grid: &'a Grid<T>,
+    next: Point,
+    step: Point,
+}
+
+impl<'a, T> Iterator for Line<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.next;
+        let cell = self.grid.get(p)?;
+        self.next += self.step;
+        Some((p, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+abc
+def";
+
+    fn values<'a>(line: impl Iterator<Item = (Point, &'a char)>) -> String {
+        line.map(|(_, &c)| c).collect()
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        let grid = Grid::parse(SAMPLE).unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.to_string(), SAMPLE);
+    }
+
+    #[test]
+    fn test_parse_rejects_ragged_rows() {
+        assert_eq!(
+            Grid::parse("abc\nde\n"),
+            Err(ShapeError { row: 1, expected: 3, found: 2 })
+        );
+    }
+
+    #[test]
+    fn test_shape_error_converts_to_parse_error() {
+        let err: ParseError = Grid::parse("abc\nde\nfgh").unwrap_err().into();
+        assert_eq!(err.to_string(), "line 2, column 3: row has 2 cells, expected 3");
+    }
+
+    #[test]
+    fn test_parse_ignores_crlf_and_trailing_newline() {
+        let grid = Grid::parse("ab\r\ncd\r\n").unwrap();
+        assert_eq!(grid.to_string(), "ab\ncd");
+    }
+
+    #[test]
+    fn test_get_is_bounds_checked() {
+        let grid = Grid::parse(SAMPLE).unwrap();
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid[Point::new(1, 0)], 'b');
+    }
+
+    #[test]
+    fn test_neighbours_stay_in_bounds() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours4(Point::ZERO).count(), 2);
+        assert_eq!(grid.neighbours8(Point::ZERO).count(), 3);
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = Grid::parse(SAMPLE).unwrap();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(values(grid.column(2)), "cf");
+        assert_eq!(grid.columns().map(values).collect::<Vec<_>>(), ["ad", "be", "cf"]);
+        assert_eq!(grid.diagonals().map(values).collect::<Vec<_>>(), ["ae", "bf", "c", "d"]);
+        assert_eq!(grid.anti_diagonals().map(values).collect::<Vec<_>>(), ["a", "bd", "ce", "f"]);
+        assert_eq!(values(grid.ray(Point::new(2, 1), Point::LEFT)), "fed");
+    }
+
+    #[test]
+    fn test_find_helpers() {
+        let grid = Grid::parse("a#b\n#c#").unwrap();
+        assert_eq!(grid.position_of(&'c'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(|&c| c == 'z'), None);
+        assert_eq!(
+            grid.find_all(|&c| c == '#').collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1), Point::new(2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid = Grid::parse("").unwrap();
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid.points().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+}
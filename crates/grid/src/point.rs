@@ -0,0 +1,132 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position (or offset) on a grid. `x` grows to the right, `y` grows down.
+///
+/// Coordinates are signed so that stepping off the top or left edge yields a
+/// point that [`Grid::get`](crate::Grid::get) simply rejects, instead of an
+/// underflowing `usize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ZERO: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+    pub const RIGHT: Point = Point::new(1, 0);
+    pub const UP_LEFT: Point = Point::new(-1, -1);
+    pub const UP_RIGHT: Point = Point::new(1, -1);
+    pub const DOWN_LEFT: Point = Point::new(-1, 1);
+    pub const DOWN_RIGHT: Point = Point::new(1, 1);
+
+    /// The four orthogonal directions, clockwise from up.
+    pub const ORTHOGONAL: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+
+    /// All eight directions, clockwise from up.
+    pub const ALL_DIRECTIONS: [Point; 8] = [
+        Point::UP,
+        Point::UP_RIGHT,
+        Point::RIGHT,
+        Point::DOWN_RIGHT,
+        Point::DOWN,
+        Point::DOWN_LEFT,
+        Point::LEFT,
+        Point::UP_LEFT,
+    ];
+
+    pub const fn new(x: isize, y: isize) -> Point {
+        Point { x, y }
+    }
+
+    /// Rotates a direction 90 degrees clockwise (screen coordinates).
+    pub fn turn_right(self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Rotates a direction 90 degrees counter-clockwise (screen coordinates).
+    pub fn turn_left(self) -> Point {
+        Point::new(self.y, -self.x)
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Point {
+        Point::new(x as isize, y as isize)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, k: isize) -> Point {
+        Point::new(self.x * k, self.y * k)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns_cycle_through_orthogonal_directions() {
+        let mut dir = Point::UP;
+        for expected in [Point::RIGHT, Point::DOWN, Point::LEFT, Point::UP] {
+            dir = dir.turn_right();
+            assert_eq!(dir, expected);
+        }
+        assert_eq!(Point::UP.turn_left(), Point::LEFT);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + Point::UP, Point::new(2, 2));
+        assert_eq!(p - Point::new(5, 5), Point::new(-3, -2));
+        assert_eq!(Point::DOWN_LEFT * 3, Point::new(-3, 3));
+        assert_eq!(-Point::DOWN_LEFT, Point::UP_RIGHT);
+        assert_eq!(p.manhattan(Point::ZERO), 5);
+    }
+}
@@ -0,0 +1,223 @@
+#!/usr/bin/env python3
+"""
+Extract worked examples and their expected answers from a day's puzzle prose.
+
+Writes example<n>.txt next to the day's markdown and records the answers the
+prose gives in examples.toml, which `aoc verify --examples` (and the runner's
+test suite) checks every Rust day against.
+
+Usage: python scripts/extract_examples.py <year> [day ...] [--force]
+
+Both steps are heuristics over copied puzzle text, so review the output:
+the script prints what it found, and examples.toml is meant to be edited.
+"""
+
+import argparse
+import re
+import sys
+from pathlib import Path
+
+# A line that reads like a sentence: four or more words ending in punctuation.
+PROSE_LINE = re.compile(r"[A-Za-z']+(?:[ ,-]+[A-Za-z'()]+){3,}.*[.!?:;)]$")
+
+# Intros that show an earlier example again (annotated, solved, reworked)
+# rather than introducing a new input.
+BACK_REFERENCE = re.compile(
+    r"\b(same|again|above|before|first example|original example|"
+    r"this example|these example)\b",
+    re.IGNORECASE,
+)
+
+# A standalone number: not part of a word, a decimal or an expression.
+NUMBER = re.compile(r"(?<![\w.*+=|-])\d+(?![\w*+=|-]|\.\d)")
+
+PART_TWO = re.compile(r"^--- Part Two ---")
+
+FIXTURE_HEADER = """\
+# Expected answers for the worked examples in the puzzle text, checked by
+# `aoc verify --examples`. Generated by scripts/extract_examples.py and then
+# reviewed; each [exampleN] table refers to exampleN.txt in this directory.
+"""
+
+
+def is_prose(line):
+    return bool(PROSE_LINE.match(line.strip()))
+
+
+def is_label(line):
+    # "Initial arrangement:" or "After 1 blink:" above a block of output
+    line = line.strip()
+    return line.endswith(":") and not is_prose(line)
+
+
+def example_blocks(lines):
+    """Yield (line index, intro, block) for each example in the prose.
+
+    An example is the run of non-prose lines after a line ending in ':'
+    that mentions an example. A single blank line inside the run is kept
+    when more data follows it (inputs with two sections), and leading
+    labels such as "Initial arrangement:" are dropped.
+    """
+    i = 0
+    while i < len(lines):
+        intro = lines[i].strip()
+        i += 1
+        if not (intro.endswith(":") and "example" in intro.lower()):
+            continue
+        while i < len(lines) and not lines[i].strip():
+            i += 1
+        while i < len(lines) and is_label(lines[i]):
+            i += 1
+
+        start = i
+        block = []
+        while i < len(lines):
+            line = lines[i]
+            if line.strip() and not is_prose(line):
+                block.append(line.rstrip())
+                i += 1
+            elif (
+                not line.strip()
+                and block
+                and i + 1 < len(lines)
+                and lines[i + 1].strip()
+                and not is_prose(lines[i + 1])
+                and not is_label(lines[i + 1])
+            ):
+                block.append("")
+                i += 1
+            else:
+                break
+        if block:
+            yield start, intro, "\n".join(block)
+
+
+def extract_examples(prose):
+    """Return the distinct example inputs in the prose, in order."""
+    examples = []
+    for _, intro, block in example_blocks(prose.splitlines()):
+        if not BACK_REFERENCE.search(intro) and block not in examples:
+            examples.append(block)
+    return examples
+
+
+def answer_in(line):
+    """The last standalone number in a sentence, ignoring parentheticals."""
+    text = re.sub(r"\([^)]*\)", "", line)
+    numbers = NUMBER.findall(text)
+    return numbers[-1] if numbers else None
+
+
+def extract_answers(prose):
+    """Return {part: (answer, example number)} for the parts the prose solves.
+
+    Each part ends with a question; its example answer is the last number in
+    the nearest sentence before it that has one. That answer belongs to the
+    latest new example shown before it, unless the sentence refers back to
+    the original one.
+    """
+    lines = prose.splitlines()
+    examples = extract_examples(prose)
+    # Where each distinct example first appears
+    starts = {}
+    for start, intro, block in example_blocks(lines):
+        if block in examples and block not in starts.values():
+            starts[start] = block
+
+    split = next((i for i, line in enumerate(lines) if PART_TWO.match(line)), len(lines))
+    sections = {1: (0, split)}
+    if split < len(lines):
+        sections[2] = (split + 1, len(lines))
+
+    answers = {}
+    for part, (begin, end) in sections.items():
+        questions = [
+            i for i in range(begin, end) if "?" in lines[i] and is_prose(lines[i])
+        ]
+        i = questions[-1] - 1 if questions else end - 1
+        while i >= begin:
+            line = lines[i]
+            if is_prose(line) and answer_in(line) is not None:
+                break
+            i -= 1
+        if i < begin:
+            continue
+
+        answer = answer_in(lines[i])
+        shown = [block for start, block in sorted(starts.items()) if start < i]
+        if not shown:
+            continue
+        if re.search(r"\b(original|first) example\b", lines[i], re.IGNORECASE):
+            example = examples[0]
+        else:
+            example = shown[-1]
+        answers[part] = (answer, examples.index(example) + 1)
+    return answers
+
+
+def render_fixture(answers):
+    by_example = {}
+    for part, (answer, example) in sorted(answers.items()):
+        by_example.setdefault(example, []).append((part, answer))
+
+    tables = []
+    for example, parts in sorted(by_example.items()):
+        rows = "".join(f"part{part} = {answer}\n" for part, answer in parts)
+        tables.append(f"[example{example}]\n{rows}")
+    return FIXTURE_HEADER + "".join("\n" + table for table in tables)
+
+
+def prose_path(day_dir, day):
+    for name in ("README.md", f"day{day}.md"):
+        if (day_dir / name).exists():
+            return day_dir / name
+    return None
+
+
+def write_fixtures(day_dir, prose, force=False):
+    """Write exampleN.txt and examples.toml for a day; return the answers."""
+    examples = extract_examples(prose)
+    for n, example in enumerate(examples, start=1):
+        example_path = day_dir / f"example{n}.txt"
+        if force or not example_path.exists():
+            example_path.write_text(example + "\n")
+            print(f"Created {example_path}")
+
+    answers = extract_answers(prose)
+    fixture_path = day_dir / "examples.toml"
+    if answers and (force or not fixture_path.exists()):
+        fixture_path.write_text(render_fixture(answers))
+        print(f"Created {fixture_path}")
+    elif fixture_path.exists():
+        print(f"Skipped {fixture_path} (exists)")
+
+    for part, (answer, example) in sorted(answers.items()):
+        print(f"  part {part}: {answer} from example{example}.txt")
+    return answers
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Extract AoC examples into fixtures")
+    parser.add_argument("year", type=int, help="Year (e.g. 2024)")
+    parser.add_argument("days", type=int, nargs="*", help="Days (default: every day found)")
+    parser.add_argument("--force", action="store_true",
+                        help="Overwrite existing example and fixture files")
+    args = parser.parse_args()
+
+    year_dir = Path(__file__).parent.parent / str(args.year)
+    days = args.days or sorted(
+        int(path.name[3:]) for path in year_dir.glob("day*") if path.name[3:].isdigit()
+    )
+
+    for day in days:
+        day_dir = year_dir / f"day{day}"
+        path = prose_path(day_dir, day)
+        if path is None:
+            print(f"{args.year} day {day}: no puzzle text, skipping", file=sys.stderr)
+            continue
+        print(f"{args.year} day {day} ({path.name})")
+        write_fixtures(day_dir, path.read_text(), args.force)
+
+
+if __name__ == "__main__":
+    main()
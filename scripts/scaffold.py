@@ -1,7 +1,7 @@
 #!/usr/bin/env python3
 """
 Scaffold a new Advent of Code day.
-Usage: python scripts/scaffold.py [year] [day]
+Usage: python scripts/scaffold.py [--lang python|rust] [year] [day]
 If year/day are not provided, defaults to today (EST).
 """
 
@@ -11,6 +11,8 @@ import os
 import sys
 from pathlib import Path
 
+from extract_examples import extract_examples
+
 try:
     from aocd import get_data
     from dotenv import load_dotenv
@@ -77,6 +79,143 @@ README_TEMPLATE = '''# Advent of Code {year} — Day {day}
 - Input is saved to `input.txt` when you run the script.
 '''
 
+RUST_CARGO_TEMPLATE = """[package]
+name = "{package}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aoc-core = {{ path = "../../crates/aoc-core" }}
+log = "0.4"
+"""
+
+RUST_TEMPLATE = """use aoc_core::{{Answer, ParseError, Solution}};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    const YEAR: u16 = {year};
+    const DAY: u8 = {day};
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {{
+        Ok(input.lines().map(String::from).collect())
+    }}
+
+    // Registered with the runner straight away, so the stubs answer
+    // rather than panic under `aoc run` and `aoc bench`
+    fn part1(_lines: &Self::Input) -> Answer {{
+        Answer::from("unsolved")
+    }}
+
+    fn part2(_lines: &Self::Input) -> Answer {{
+        Answer::from("unsolved")
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+{examples}{tests}}}
+"""
+
+RUST_EXAMPLE_TEMPLATE = """
+    const {name}: &str = "\\
+{text}";
+"""
+
+RUST_TEST_TEMPLATE = """
+    #[test]
+    #[ignore = "fill in the expected answer from the puzzle text"]
+    fn test_part{part}_example{suffix}() {{
+        assert_eq!(Day{day}::part{part}(&Day{day}::parse({name}).unwrap()).to_string(), "");
+    }}
+"""
+
+def rust_string_literal_body(text):
+    return text.replace("\\", "\\\\").replace('"', '\\"')
+
+
+def rust_package(year, day):
+    # The 2024 crates predate multiple Rust years and keep their short names.
+    return f"day{day}" if year == 2024 else f"aoc{year}-day{day}"
+
+
+def rust_crate(year, day):
+    return rust_package(year, day).replace("-", "_")
+
+
+def register_rust_day(base_dir, year, day):
+    """Add the day to the workspace and the runner's registry."""
+    package = rust_package(year, day)
+
+    workspace = base_dir / "Cargo.toml"
+    content = workspace.read_text()
+    if f'"{year}/day*"' not in content and f'"{year}/day{day}"' not in content:
+        start = content.index("members = [")
+        end = content.index("]", start)
+        content = content[:end] + f'    "{year}/day{day}",\n' + content[end:]
+        workspace.write_text(content)
+        print(f"Added {year}/day{day} to {workspace}")
+
+    runner_manifest = base_dir / "crates" / "aoc" / "Cargo.toml"
+    content = runner_manifest.read_text()
+    if f"{package} = " not in content:
+        content = content.rstrip("\n") + f'\n{package} = {{ path = "../../{year}/day{day}" }}\n'
+        runner_manifest.write_text(content)
+        print(f"Added {package} to {runner_manifest}")
+
+    registry = base_dir / "crates" / "aoc" / "src" / "days.rs"
+    content = registry.read_text()
+    entry = f"Day::of::<{rust_crate(year, day)}::Day{day}>()"
+    if entry not in content:
+        content = content.replace("    ]\n}", f"        {entry},\n    ]\n}}", 1)
+        registry.write_text(content)
+        print(f"Registered Day{day} in {registry}")
+
+
+def scaffold_rust(base_dir, day_dir, readme_path, year, day):
+    cargo_path = day_dir / "Cargo.toml"
+    lib_path = day_dir / "src" / "lib.rs"
+
+    if not cargo_path.exists():
+        cargo_path.write_text(RUST_CARGO_TEMPLATE.format(package=rust_package(year, day)))
+        print(f"Created {cargo_path}")
+    else:
+        print(f"Skipped {cargo_path} (exists)")
+
+    examples = extract_examples(readme_path.read_text()) if readme_path.exists() else []
+    for n, example in enumerate(examples, start=1):
+        example_path = day_dir / f"example{n}.txt"
+        if not example_path.exists():
+            example_path.write_text(example + "\n")
+            print(f"Created {example_path}")
+
+    if not lib_path.exists():
+        names = ["TEST_INPUT"] + [f"TEST_INPUT_{n}" for n in range(2, len(examples) + 1)]
+        blocks = "".join(
+            RUST_EXAMPLE_TEMPLATE.format(name=name, text=rust_string_literal_body(example))
+            for name, example in zip(names, examples or [""])
+        )
+        # Both parts against the first example, part 1 against any others
+        tests = [(1, "", "TEST_INPUT"), (2, "", "TEST_INPUT")]
+        tests += [(1, f"_{n}", f"TEST_INPUT_{n}") for n in range(2, len(examples) + 1)]
+        tests = "".join(
+            RUST_TEST_TEMPLATE.format(day=day, part=part, suffix=suffix, name=name)
+            for part, suffix, name in tests
+        )
+        lib_path.parent.mkdir(parents=True, exist_ok=True)
+        lib_path.write_text(RUST_TEMPLATE.format(year=year, day=day, examples=blocks, tests=tests))
+        print(f"Created {lib_path} with {len(examples)} example(s)")
+    else:
+        print(f"Skipped {lib_path} (exists)")
+
+    register_rust_day(base_dir, year, day)
+    print(f"Once the day is solved, run `python scripts/extract_examples.py {year} {day}` "
+          "to record the example answers for `aoc verify --examples`.")
+
+
 def get_est_date():
     # AoC unlocks at midnight EST (UTC-5)
     utc_now = datetime.datetime.utcnow()
@@ -87,6 +226,8 @@ def main():
     parser = argparse.ArgumentParser(description="Scaffold AoC day")
     parser.add_argument("year", type=int, nargs="?", help="Year (e.g. 2025)")
     parser.add_argument("day", type=int, nargs="?", help="Day (1-25)")
+    parser.add_argument("--lang", choices=["python", "rust"], default="python",
+                        help="Solution language (default: python)")
     args = parser.parse_args()
 
     now = get_est_date()
@@ -108,14 +249,15 @@ def main():
     # Create directory
     day_dir.mkdir(parents=True, exist_ok=True)
 
-    # Create script if missing
-    if not script_path.exists():
-        script_content = TEMPLATE.format(year=year, day=day)
-        script_path.write_text(script_content)
-        script_path.chmod(0o755)
-        print(f"Created {script_path}")
-    else:
-        print(f"Skipped {script_path} (exists)")
+    # Create script if missing (Rust sources are generated once the prose is available)
+    if args.lang == "python":
+        if not script_path.exists():
+            script_content = TEMPLATE.format(year=year, day=day)
+            script_path.write_text(script_content)
+            script_path.chmod(0o755)
+            print(f"Created {script_path}")
+        else:
+            print(f"Skipped {script_path} (exists)")
 
     # Create README if missing
     if not readme_path.exists():
@@ -147,6 +289,9 @@ def main():
     except Exception as e:
         print(f"Warning: Could not fetch puzzle text: {e}")
 
+    if args.lang == "rust":
+        scaffold_rust(base_dir, day_dir, readme_path, year, day)
+
     print("Done!")
 
 if __name__ == "__main__":